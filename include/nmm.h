@@ -0,0 +1,122 @@
+#ifndef NMM_H
+#define NMM_H
+
+/* Written by cbindgen from src/capi.rs, don't edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef enum NmmResult {
+  NMM_RESULT_OK = 0,
+  NMM_RESULT_UNREADABLE,
+  NMM_RESULT_GAME_OVER,
+  NMM_RESULT_NOT_YOUR_TURN,
+  NMM_RESULT_NO_MAN_TO_MOVE,
+  NMM_RESULT_STILL_PLACING,
+  NMM_RESULT_NOT_ON_BOARD,
+  NMM_RESULT_OCCUPIED,
+  NMM_RESULT_NOT_ADJACENT,
+  NMM_RESULT_INVALID_REMOVAL,
+  NMM_RESULT_TOO_MANY_REMOVALS,
+  NMM_RESULT_MISSING_REMOVAL,
+} NmmResult;
+
+typedef enum NmmStatus {
+  NMM_STATUS_PLAYING = 0,
+  NMM_STATUS_PLAYER_ONE_WINS,
+  NMM_STATUS_PLAYER_TWO_WINS,
+  NMM_STATUS_DRAW,
+} NmmStatus;
+
+typedef enum NmmEndReason {
+  NMM_END_REASON_NONE = 0,
+  NMM_END_REASON_MILL_CAPTURE,
+  NMM_END_REASON_BLOCKADE,
+  NMM_END_REASON_RESIGNATION,
+  NMM_END_REASON_AGREEMENT,
+  NMM_END_REASON_REPETITION,
+  NMM_END_REASON_TIMEOUT,
+} NmmEndReason;
+
+typedef struct NmmGame NmmGame;
+
+/*
+ A new game, of Morabaraba if `morabaraba` isn't 0. Free it with `nmm_game_free`.
+ */
+struct NmmGame *nmm_game_new(int32_t morabaraba);
+
+/*
+ A game saved by `nmm_game_get_json`, or NULL if `json` isn't one.
+
+ # Safety
+ `json` must be a NUL-terminated string.
+ */
+struct NmmGame *nmm_game_load(const char *json);
+
+/*
+ # Safety
+ `game` must be NULL or a handle that hasn't already been freed.
+ */
+void nmm_game_free(struct NmmGame *game);
+
+/*
+ Plays a move for the player to move, written like "a7", "a7-a4" or "a7-a4xd2".
+
+ # Safety
+ `game` must be a live handle and `notation` a NUL-terminated string.
+ */
+enum NmmResult nmm_game_submit(struct NmmGame *game, const char *notation);
+
+/*
+ # Safety
+ `game` must be a live handle.
+ */
+size_t nmm_game_legal_move_count(const struct NmmGame *game);
+
+/*
+ The legal move at `index`, or NULL past the end. The string belongs to the game, and
+ lasts until the next move is submitted or the game is freed.
+
+ # Safety
+ `game` must be a live handle.
+ */
+const char *nmm_game_legal_move(const struct NmmGame *game, size_t index);
+
+/*
+ The game as JSON, for `nmm_game_load`. Free it with `nmm_string_free`.
+
+ # Safety
+ `game` must be a live handle.
+ */
+char *nmm_game_get_json(const struct NmmGame *game);
+
+/*
+ # Safety
+ `string` must be NULL or a string returned by `nmm_game_get_json`.
+ */
+void nmm_string_free(char *string);
+
+/*
+ 1 or 2.
+
+ # Safety
+ `game` must be a live handle.
+ */
+int32_t nmm_game_next_player(const struct NmmGame *game);
+
+/*
+ # Safety
+ `game` must be a live handle.
+ */
+enum NmmStatus nmm_game_status(const struct NmmGame *game);
+
+/*
+ # Safety
+ `game` must be a live handle.
+ */
+enum NmmEndReason nmm_game_end_reason(const struct NmmGame *game);
+
+#endif  /* NMM_H */
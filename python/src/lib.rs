@@ -0,0 +1,286 @@
+// Python classes over nmm_lib. Build with `maturin build --release` in this directory, or
+// `maturin develop` to install into the current virtualenv.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use nmm_lib::{Game as RustGame, GameMove as RustGameMove, GameStatus, Location as RustLocation};
+use nmm_lib::{Player as RustPlayer, Variant, FEATURE_COUNT};
+
+#[pyclass(eq, eq_int, hash, frozen, from_py_object)]
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+enum Player {
+    One = 1,
+    Two = 2,
+}
+
+impl From<RustPlayer> for Player {
+    fn from(player: RustPlayer) -> Player {
+        match player {
+            RustPlayer::One => Player::One,
+            RustPlayer::Two => Player::Two,
+        }
+    }
+}
+
+impl From<Player> for RustPlayer {
+    fn from(player: Player) -> RustPlayer {
+        match player {
+            Player::One => RustPlayer::One,
+            Player::Two => RustPlayer::Two,
+        }
+    }
+}
+
+// a point on the board, written like "a7", or one of "hand" and "captured"
+#[pyclass(frozen, from_py_object)]
+#[derive(Clone, Copy)]
+struct Location(RustLocation);
+
+#[pymethods]
+impl Location {
+    #[new]
+    fn new(code: &str) -> PyResult<Location> {
+        let location = match code.to_lowercase().as_str() {
+            "hand" => RustLocation::Hand,
+            "captured" => RustLocation::Captured,
+            _ => RustLocation::parse(code)
+                .ok_or_else(|| PyValueError::new_err(format!("{} isn't a point on the board", code)))?,
+        };
+        Ok(Location(location))
+    }
+
+    // the 24 points, from a7 to g1
+    #[staticmethod]
+    fn points() -> Vec<Location> {
+        RustLocation::get_points().into_iter().map(Location).collect()
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_str().to_lowercase()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Location('{}')", self.__str__())
+    }
+
+    fn __eq__(&self, other: &Location) -> bool {
+        self.0 == other.0
+    }
+
+    fn __hash__(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.0.to_str().hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+#[pyclass(frozen, skip_from_py_object)]
+#[derive(Clone)]
+struct GameMove(RustGameMove);
+
+#[pymethods]
+impl GameMove {
+    #[new]
+    #[pyo3(signature = (player, from_, to, removals = Vec::new()))]
+    fn new(player: Player, from_: Location, to: Location, removals: Vec<Location>) -> PyResult<GameMove> {
+        // men are placed from the hand, but everything else happens on the board
+        let from_ = match from_.0 {
+            RustLocation::Hand => RustLocation::Hand,
+            location => on_board(location)?,
+        };
+        let removals = removals.into_iter()
+            .map(|location| on_board(location.0))
+            .collect::<PyResult<Vec<RustLocation>>>()?;
+        Ok(GameMove(RustGameMove::with_removals(player.into(), from_, on_board(to.0)?, removals)))
+    }
+
+    // "a7", "a7-a4" or "a7-a4xd2"
+    #[staticmethod]
+    fn from_notation(player: Player, notation: &str) -> PyResult<GameMove> {
+        RustGameMove::from_notation(player.into(), notation)
+            .map(GameMove)
+            .ok_or_else(|| PyValueError::new_err(format!("didn't understand \"{}\"", notation)))
+    }
+
+    fn get_player(&self) -> Player {
+        self.0.get_player().into()
+    }
+
+    fn get_from(&self) -> Location {
+        Location(self.0.get_from())
+    }
+
+    fn get_to(&self) -> Location {
+        Location(self.0.get_to())
+    }
+
+    fn get_removals(&self) -> Vec<Location> {
+        self.0.get_removals().into_iter().map(Location).collect()
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("GameMove('{}')", self.0)
+    }
+
+    fn __eq__(&self, other: &GameMove) -> bool {
+        self.0 == other.0
+    }
+}
+
+fn on_board(location: RustLocation) -> PyResult<RustLocation> {
+    if RustLocation::get_points().contains(&location) {
+        Ok(location)
+    } else {
+        Err(PyValueError::new_err(format!("{} isn't a point on the board", Location(location).__str__())))
+    }
+}
+
+#[pyclass(skip_from_py_object)]
+#[derive(Clone)]
+struct Game(RustGame);
+
+#[pymethods]
+impl Game {
+    #[new]
+    #[pyo3(signature = (morabaraba = false))]
+    fn new(morabaraba: bool) -> Game {
+        let variant = if morabaraba { Variant::Morabaraba } else { Variant::NineMensMorris };
+        Game(RustGame::with_variant(variant))
+    }
+
+    #[staticmethod]
+    fn load(json: &str) -> PyResult<Game> {
+        RustGame::load(json)
+            .map(Game)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    fn get_json(&self) -> String {
+        self.0.get_json()
+    }
+
+    fn legal_moves(&self) -> Vec<GameMove> {
+        self.0.get_legal_moves().into_iter().map(GameMove).collect()
+    }
+
+    // false if the move was turned down
+    fn submit(&mut self, game_move: &GameMove) -> bool {
+        self.0.submit(game_move.0.clone())
+    }
+
+    // why submit would turn the move down, or None if it wouldn't
+    fn validate(&self, game_move: &GameMove) -> Option<String> {
+        self.0.validate(&game_move.0).err().map(|e| e.to_string())
+    }
+
+    fn get_next_player(&self) -> Player {
+        self.0.get_next_player().into()
+    }
+
+    fn get_turn(&self) -> u32 {
+        self.0.get_turn()
+    }
+
+    // "playing", "win" or "draw"
+    fn get_status(&self) -> &'static str {
+        match self.0.get_status() {
+            GameStatus::Playing => "playing",
+            GameStatus::Win(_, _) => "win",
+            GameStatus::Draw(_) => "draw",
+        }
+    }
+
+    fn get_winner(&self) -> Option<Player> {
+        match self.0.get_status() {
+            GameStatus::Win(player, _) => Some(player.into()),
+            _ => None,
+        }
+    }
+
+    // the position as seen by the player to move, FEATURE_COUNT floats long; see
+    // Game::encode_features in nmm_lib for the layout
+    fn encode_features(&self) -> Vec<f32> {
+        self.0.encode_features()
+    }
+
+    fn copy(&self) -> Game {
+        self.clone()
+    }
+
+    fn __copy__(&self) -> Game {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Game {
+        self.clone()
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+// a small xorshift generator, so that playouts with the same seed play the same games
+struct Random(u64);
+
+impl Random {
+    fn below(&mut self, bound: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % bound as u64) as usize
+    }
+}
+
+// plays `count` games on from `game` with uniformly random legal moves, and returns how many
+// player 1 won, player 2 won, how many were drawn, and how many were still going after
+// `max_moves`
+#[pyfunction]
+#[pyo3(signature = (game, count, seed = 1, max_moves = 200))]
+fn random_playouts(py: Python<'_>, game: &Game, count: usize, seed: u64, max_moves: usize) -> (usize, usize, usize, usize) {
+    let start = game.0.clone();
+    py.detach(|| {
+        let mut random = Random(seed.max(1));
+        let mut results = (0, 0, 0, 0);
+
+        for _ in 0..count {
+            let mut playout = start.clone();
+            for _ in 0..max_moves {
+                let moves = playout.get_legal_moves();
+                if moves.is_empty() {
+                    break;
+                }
+                let index = random.below(moves.len());
+                playout.submit(moves[index].clone());
+            }
+
+            match playout.get_status() {
+                GameStatus::Win(RustPlayer::One, _) => results.0 += 1,
+                GameStatus::Win(RustPlayer::Two, _) => results.1 += 1,
+                GameStatus::Draw(_) => results.2 += 1,
+                GameStatus::Playing => results.3 += 1,
+            }
+        }
+
+        results
+    })
+}
+
+#[pymodule]
+fn nmm(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<Player>()?;
+    module.add_class::<Location>()?;
+    module.add_class::<GameMove>()?;
+    module.add_class::<Game>()?;
+    module.add_function(wrap_pyfunction!(random_playouts, module)?)?;
+    module.add("FEATURE_COUNT", FEATURE_COUNT)?;
+    Ok(())
+}
@@ -0,0 +1,82 @@
+import copy
+import unittest
+
+from nmm import FEATURE_COUNT, Game, GameMove, Location, Player, random_playouts
+
+
+class GameTest(unittest.TestCase):
+    def test_every_empty_point_is_a_legal_placement_at_the_start(self):
+        moves = Game().legal_moves()
+        self.assertEqual(len(moves), 24)
+        self.assertIn(GameMove(Player.One, Location("hand"), Location("a7")), moves)
+
+    def test_moves_are_played_in_turn(self):
+        game = Game()
+        self.assertTrue(game.submit(GameMove.from_notation(Player.One, "a7")))
+        self.assertEqual(game.get_next_player(), Player.Two)
+        self.assertFalse(game.submit(GameMove.from_notation(Player.Two, "a7")))
+        self.assertEqual(
+            game.validate(GameMove.from_notation(Player.Two, "a7")),
+            "that point is already taken",
+        )
+
+    def test_copies_are_played_separately(self):
+        game = Game()
+        for copied in (game.copy(), copy.copy(game), copy.deepcopy(game)):
+            copied.submit(GameMove.from_notation(Player.One, "a7"))
+            self.assertEqual(copied.get_turn(), 1)
+        self.assertEqual(game.get_turn(), 0)
+
+    def test_games_survive_a_round_trip_through_json(self):
+        game = Game(morabaraba=True)
+        game.submit(GameMove.from_notation(Player.One, "d6"))
+        self.assertEqual(Game.load(game.get_json()).get_json(), game.get_json())
+
+    def test_features_are_seen_from_the_side_to_move(self):
+        game = Game()
+        game.submit(GameMove.from_notation(Player.One, "a7"))
+        features = game.encode_features()
+        self.assertEqual(len(features), FEATURE_COUNT)
+        # a7 is the opponent's, from where player two sits
+        self.assertEqual(features[24], 1.0)
+        self.assertEqual(features[72:74], [9.0, 8.0])
+        self.assertEqual(features[77], 1.0)
+
+    def test_random_playouts_are_repeatable(self):
+        game = Game()
+        results = random_playouts(game, 20, seed=7)
+        self.assertEqual(sum(results), 20)
+        self.assertEqual(random_playouts(game, 20, seed=7), results)
+        self.assertEqual(game.get_turn(), 0)
+
+    def test_random_playouts_can_run_past_255_moves(self):
+        results = random_playouts(Game(), 5, seed=5, max_moves=2000)
+        self.assertEqual(sum(results), 5)
+
+
+class GameMoveTest(unittest.TestCase):
+    def test_moves_only_place_from_the_hand(self):
+        with self.assertRaises(ValueError):
+            GameMove(Player.One, Location("a7"), Location("hand"))
+        with self.assertRaises(ValueError):
+            GameMove(Player.One, Location("captured"), Location("a7"))
+
+    def test_only_points_on_the_board_can_be_removed(self):
+        for removal in ("hand", "captured"):
+            with self.assertRaises(ValueError):
+                GameMove(Player.One, Location("hand"), Location("a7"), [Location(removal)])
+
+
+class LocationTest(unittest.TestCase):
+    def test_locations_are_written_in_lowercase(self):
+        self.assertEqual(str(Location("A7")), "a7")
+        self.assertEqual(Location("a7"), Location("A7"))
+        self.assertEqual(len(set(Location.points())), 24)
+
+    def test_unknown_points_are_rejected(self):
+        with self.assertRaises(ValueError):
+            Location("z9")
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,151 @@
+use std::fmt;
+use {Game, GameStatus, Location, Player};
+
+const DIAGRAM: [&str; 14] = [
+    "7 o-----------o-----------o",
+    "  |           |           |",
+    "6 |   o-------o-------o   |",
+    "  |   |       |       |   |",
+    "5 |   |   o---o---o   |   |",
+    "  |   |   |       |   |   |",
+    "4 o---o---o       o---o---o",
+    "  |   |   |       |   |   |",
+    "3 |   |   o---o---o   |   |",
+    "  |   |       |       |   |",
+    "2 |   o-------o-------o   |",
+    "  |           |           |",
+    "1 o-----------o-----------o",
+    "  a   b   c   d   e   f   g"
+];
+
+// where to draw a diagonal, by the points it joins
+const DIAGONALS: [(Location, Location, char); 8] = [
+    (Location::A7, Location::B6, '\\'),
+    (Location::B6, Location::C5, '\\'),
+    (Location::G7, Location::F6, '/'),
+    (Location::F6, Location::E5, '/'),
+    (Location::A1, Location::B2, '/'),
+    (Location::B2, Location::C3, '/'),
+    (Location::G1, Location::F2, '\\'),
+    (Location::F2, Location::E3, '\\')
+];
+
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.render_ascii(None))
+    }
+}
+
+impl Game {
+    // men are drawn as 1 and 2, the last move is wrapped in ( ), a selected man in [ ]
+    // and the points it can move to are marked with *
+    pub fn render_ascii(&self, selected: Option<Location>) -> String {
+        // one spare column so a point on the g file can be surrounded
+        let mut grid: Vec<Vec<char>> = DIAGRAM.iter().map(|line| {
+            line.chars().chain(" ".chars()).collect()
+        }).collect();
+
+        for &(a, b, diagonal) in DIAGONALS.iter() {
+            if self.board.is_adjacent(a, b) {
+                let ((a_row, a_col), (b_row, b_col)) = (Self::get_cell(a), Self::get_cell(b));
+                grid[(a_row + b_row) / 2][(a_col + b_col) / 2] = diagonal;
+            }
+        }
+
+        for location in Location::get_points() {
+            let (row, col) = Self::get_cell(location);
+            grid[row][col] = self.get_symbol(location);
+        }
+
+        let destinations = match selected {
+            Some(location) => self.get_destinations(location),
+            None => vec!()
+        };
+        for location in destinations {
+            let (row, col) = Self::get_cell(location);
+            grid[row][col] = '*';
+        }
+
+        if let Some(last_move) = self.history.last() {
+            for &location in [last_move.get_from(), last_move.get_to()].iter() {
+                Self::surround(&mut grid, location, '(', ')');
+            }
+        }
+
+        if let Some(location) = selected {
+            Self::surround(&mut grid, location, '[', ']');
+        }
+
+        let mut lines: Vec<String> = grid.iter().map(|line| {
+            line.iter().collect::<String>().trim_end().to_string()
+        }).collect();
+        lines.push(String::new());
+        lines.push(self.get_player_summary(Player::One));
+        lines.push(self.get_player_summary(Player::Two));
+        lines.push(self.get_status_summary());
+
+        lines.join("\n") + "\n"
+    }
+
+    fn get_destinations(&self, from: Location) -> Vec<Location> {
+        let owner = self.pieces.iter()
+            .find(|&piece| piece.get_location() == from)
+            .map(|piece| piece.get_player());
+
+        match owner {
+            Some(player) => self.board.get_points().into_iter().filter(|&to| {
+                self.is_valid_move(player, from, to)
+            }).collect(),
+            None => vec!()
+        }
+    }
+
+    fn get_symbol(&self, location: Location) -> char {
+        if !self.board.contains(location) {
+            return ' ';
+        }
+
+        let piece = self.pieces.iter().find(|&piece| piece.get_location() == location);
+        match piece.map(|piece| piece.get_player()) {
+            Some(Player::One)   => '1',
+            Some(Player::Two)   => '2',
+            None                => 'o'
+        }
+    }
+
+    fn get_player_summary(&self, player: Player) -> String {
+        let count = |location| self.pieces.iter().filter(|&piece| {
+            piece.get_player() == player && piece.get_location() == location
+        }).count();
+
+        format!(
+            "player {}: {} in hand, {} captured",
+            Self::wrap_player(player),
+            count(Location::Hand),
+            count(Location::Captured)
+        )
+    }
+
+    fn get_status_summary(&self) -> String {
+        match self.get_status() {
+            GameStatus::Playing     => format!("player {} to move", Self::wrap_player(self.next_player)),
+            GameStatus::Win(player, _) => format!("player {} wins", Self::wrap_player(player)),
+            GameStatus::Draw(_)     => String::from("draw")
+        }
+    }
+
+    fn surround(grid: &mut [Vec<char>], location: Location, left: char, right: char) {
+        if location == Location::Hand || location == Location::Captured {
+            return;
+        }
+
+        let (row, col) = Self::get_cell(location);
+        grid[row][col - 1] = left;
+        grid[row][col + 1] = right;
+    }
+
+    fn get_cell(location: Location) -> (usize, usize) {
+        let (file, rank) = location.get_coordinates().unwrap();
+        (2 * (6 - rank as usize), 2 + 4 * file as usize)
+    }
+}
@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+use std::sync::mpsc::Sender;
+use serde_json::Value;
+use nmm_lib::{Game, GameAction, GameMove, GameStatus, Player, Variant};
+
+// somewhere a connection sits: a game, and the side it plays, if it isn't just watching
+#[derive(Clone, Copy)]
+pub struct Seat {
+    game: u64,
+    player: Option<Player>
+}
+
+pub struct Connection {
+    pub id: usize,
+    pub sender: Sender<String>,
+    pub seat: Option<Seat>
+}
+
+struct Room {
+    game: Game,
+    // which sides have someone playing them
+    taken: [bool; 2],
+    watchers: Vec<(usize, Sender<String>)>
+}
+
+#[derive(Default)]
+pub struct Lobby {
+    rooms: HashMap<u64, Room>,
+    next_id: u64
+}
+
+impl Lobby {
+    pub fn handle(&mut self, connection: &mut Connection, line: &str) {
+        let request: Value = match serde_json::from_str(line) {
+            Ok(request) => request,
+            Err(e) => return Self::error(connection, &format!("requests are JSON objects: {}", e))
+        };
+
+        match request["command"].as_str().unwrap_or_default() {
+            "create"        => self.create(connection, &request),
+            "join"          => self.join(connection, &request, false),
+            "spectate"      => self.join(connection, &request, true),
+            "move"          => self.play(connection, &request),
+            "resign"        => self.act(connection, GameAction::Resign),
+            "offer_draw"    => self.act(connection, GameAction::OfferDraw),
+            "accept_draw"   => self.act(connection, GameAction::AcceptDraw),
+            "decline_draw"  => self.act(connection, GameAction::DeclineDraw),
+            command         => Self::error(connection, &format!("unknown command \"{}\"", command))
+        }
+    }
+
+    // frees the connection's seat so someone else can take it
+    pub fn leave(&mut self, connection: &mut Connection) {
+        let seat = match connection.seat.take() {
+            Some(seat) => seat,
+            None => return
+        };
+
+        if let Some(room) = self.rooms.get_mut(&seat.game) {
+            room.watchers.retain(|&(id, _)| id != connection.id);
+            if let Some(player) = seat.player {
+                room.taken[Self::index(player)] = false;
+            }
+        }
+
+        // a game nobody is playing or watching can't be found again, so it's dropped
+        let empty = self.rooms.get(&seat.game).is_some_and(|room| {
+            room.watchers.is_empty() && room.taken == [false; 2]
+        });
+        if empty {
+            self.rooms.remove(&seat.game);
+        }
+    }
+
+    fn create(&mut self, connection: &mut Connection, request: &Value) {
+        let variant = match request["variant"].as_str().map(str::parse::<Variant>) {
+            None => Variant::NineMensMorris,
+            Some(Ok(variant)) => variant,
+            Some(Err(e)) => return Self::error(connection, &e)
+        };
+
+        self.leave(connection);
+        self.next_id += 1;
+        self.rooms.insert(self.next_id, Room {
+            game: Game::with_variant(variant),
+            taken: [false; 2],
+            watchers: vec!()
+        });
+        self.sit(connection, self.next_id, Some(Player::One));
+    }
+
+    fn join(&mut self, connection: &mut Connection, request: &Value, spectating: bool) {
+        let id = request["game"].as_u64().unwrap_or_default();
+        let player = match self.rooms.get(&id) {
+            None => return Self::error(connection, &format!("there's no game {}", id)),
+            Some(_) if spectating => None,
+            Some(room) if !room.taken[0] => Some(Player::One),
+            Some(room) if !room.taken[1] => Some(Player::Two),
+            Some(_) => return Self::error(connection, &format!("game {} already has two players", id))
+        };
+
+        self.leave(connection);
+        self.sit(connection, id, player);
+    }
+
+    fn sit(&mut self, connection: &mut Connection, id: u64, player: Option<Player>) {
+        let room = self.rooms.get_mut(&id).unwrap();
+        if let Some(player) = player {
+            room.taken[Self::index(player)] = true;
+        }
+        room.watchers.push((connection.id, connection.sender.clone()));
+        connection.seat = Some(Seat { game: id, player });
+
+        let _ = connection.sender.send(json!({
+            "event": if player.is_some() { "joined" } else { "spectating" },
+            "game": id,
+            "player": player.map(Self::number)
+        }).to_string());
+        let _ = connection.sender.send(Self::update(id, &room.game));
+    }
+
+    fn play(&mut self, connection: &mut Connection, request: &Value) {
+        let notation = request["move"].as_str().unwrap_or_default();
+        let player = match connection.seat.and_then(|seat| seat.player) {
+            Some(player) => player,
+            None => return Self::error(connection, "join a game before playing")
+        };
+
+        match GameMove::from_notation(player, notation) {
+            Some(game_move) => self.act(connection, |_| GameAction::Move(game_move.clone())),
+            None => Self::error(connection, &format!("didn't understand \"{}\"", notation))
+        }
+    }
+
+    fn act<F>(&mut self, connection: &mut Connection, action: F) where F: Fn(Player) -> GameAction {
+        let (id, player) = match connection.seat {
+            Some(Seat { game, player: Some(player) }) => (game, player),
+            _ => return Self::error(connection, "join a game before playing")
+        };
+        let room = self.rooms.get_mut(&id).unwrap();
+        let action = action(player);
+
+        if !room.game.apply(action.clone()) {
+            let message = match action {
+                GameAction::Move(ref game_move) => match room.game.validate(game_move) {
+                    Err(error) => format!("can't play {}: {}", game_move, error),
+                    Ok(_) => format!("can't play {}", game_move)
+                },
+                _ => String::from("that isn't allowed now")
+            };
+            return Self::error(connection, &message);
+        }
+
+        let update = Self::update(id, &room.game);
+        room.watchers.retain(|(_, sender)| sender.send(update.clone()).is_ok());
+    }
+
+    fn update(id: u64, game: &Game) -> String {
+        let state: Value = serde_json::from_str(&game.get_json()).unwrap();
+        let status = match game.get_status() {
+            GameStatus::Playing => json!({ "status": "Playing" }),
+            GameStatus::Win(player, reason) => json!({
+                "status": "Win",
+                "player": Self::number(player),
+                "reason": reason.to_str()
+            }),
+            GameStatus::Draw(reason) => json!({ "status": "Draw", "reason": reason.to_str() })
+        };
+
+        json!({
+            "event": "update",
+            "game": id,
+            "state": state,
+            "status": status
+        }).to_string()
+    }
+
+    pub fn error(connection: &Connection, message: &str) {
+        let _ = connection.sender.send(json!({ "event": "error", "message": message }).to_string());
+    }
+
+    fn number(player: Player) -> u8 {
+        match player {
+            Player::One => 1,
+            Player::Two => 2
+        }
+    }
+
+    fn index(player: Player) -> usize {
+        Self::number(player) as usize - 1
+    }
+}
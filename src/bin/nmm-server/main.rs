@@ -0,0 +1,88 @@
+extern crate nmm_lib;
+#[macro_use]
+extern crate serde_json;
+
+mod lobby;
+
+use std::env;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::panic::{self, AssertUnwindSafe};
+use std::process;
+use std::sync::{mpsc, Arc, Mutex, MutexGuard};
+use std::thread;
+use lobby::{Connection, Lobby};
+
+const USAGE: &str = "usage: nmm-server [--port PORT]";
+
+fn main() {
+    let mut port = 7878;
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        match (arg.as_str(), args.next().and_then(|value| value.parse().ok())) {
+            ("--port", Some(value)) => port = value,
+            _ => {
+                eprintln!("{}", USAGE);
+                process::exit(2);
+            }
+        }
+    }
+
+    let listener = TcpListener::bind(("127.0.0.1", port)).unwrap_or_else(|e| {
+        eprintln!("couldn't listen on port {}: {}", port, e);
+        process::exit(1);
+    });
+    // port 0 picks any free port, so say which one it was
+    println!("listening on {}", listener.local_addr().unwrap());
+    io::stdout().flush().unwrap();
+
+    let lobby = Arc::new(Mutex::new(Lobby::default()));
+    for (id, stream) in listener.incoming().enumerate() {
+        let lobby = lobby.clone();
+        match stream {
+            Ok(stream) => {
+                thread::spawn(move || serve(id, stream, lobby));
+            },
+            Err(e) => eprintln!("couldn't accept a connection: {}", e)
+        }
+    }
+}
+
+// one JSON request per line in, one JSON message per line out
+fn serve(id: usize, stream: TcpStream, lobby: Arc<Mutex<Lobby>>) {
+    let mut writer = match stream.try_clone() {
+        Ok(writer) => writer,
+        Err(_) => return
+    };
+    let (sender, receiver) = mpsc::channel::<String>();
+    thread::spawn(move || {
+        for message in receiver {
+            if writeln!(writer, "{}", message).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut connection = Connection { id, sender, seat: None };
+    for line in BufReader::new(stream).lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(_) => break
+        };
+
+        // a request that panics is turned down, rather than ending the connection
+        let mut lobby = lock(&lobby);
+        let handled = panic::catch_unwind(AssertUnwindSafe(|| lobby.handle(&mut connection, &line)));
+        if handled.is_err() {
+            Lobby::error(&connection, "the server couldn't handle that request");
+        }
+    }
+
+    lock(&lobby).leave(&mut connection);
+}
+
+// a panic while the lobby was held leaves it poisoned, but the rooms are still there to use
+fn lock(lobby: &Mutex<Lobby>) -> MutexGuard<'_, Lobby> {
+    lobby.lock().unwrap_or_else(|e| e.into_inner())
+}
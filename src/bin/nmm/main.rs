@@ -0,0 +1,188 @@
+extern crate nmm_lib;
+#[cfg(feature = "tui")]
+extern crate crossterm;
+
+#[cfg(feature = "tui")]
+mod tui;
+
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::process;
+use nmm_lib::{Game, GameMove, GameStatus, Player, Variant};
+
+const USAGE: &str = "usage: nmm [--tui] [--morabaraba] [--load FILE]";
+
+const HELP: &str = "\
+moves are written with the board's coordinates:
+  a7            place a man on a7
+  a7-a4         move the man on a7 to a4
+  a7-a4xd2      ... and remove the opponent's man on d2 (repeat x for a double mill)
+commands:
+  undo          take back the last move
+  save FILE     save the game as JSON
+  load FILE     load a game saved with save
+  new           start again
+  help          show this message
+  quit          leave the game";
+
+struct Session {
+    game: Game,
+    variant: Variant,
+    // the game as it was before each move, most recent last
+    undo: Vec<String>
+}
+
+fn main() {
+    let mut variant = Variant::NineMensMorris;
+    let mut load = None;
+    let mut full_screen = false;
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--morabaraba"  => variant = Variant::Morabaraba,
+            "--load"        => load = args.next(),
+            "--tui"         => full_screen = true,
+            "--help" | "-h" => {
+                println!("{}\n\n{}", USAGE, HELP);
+                return;
+            },
+            _ => {
+                eprintln!("{}", USAGE);
+                process::exit(2);
+            }
+        }
+    }
+
+    let mut session = Session {
+        game: Game::with_variant(variant),
+        variant,
+        undo: vec!()
+    };
+
+    if let Some(path) = load {
+        if let Err(message) = session.load(&path) {
+            eprintln!("{}", message);
+            process::exit(1);
+        }
+    }
+
+    if full_screen {
+        return run_tui(session.game);
+    }
+
+    println!("type help for the list of commands\n");
+    print!("{}", session.game);
+    prompt(&session.game);
+
+    let stdin = io::stdin();
+    for line in stdin.lock().lines() {
+        let line = line.expect("couldn't read from the terminal");
+        if !session.run(line.trim()) {
+            break;
+        }
+        prompt(&session.game);
+    }
+}
+
+#[cfg(feature = "tui")]
+fn run_tui(game: Game) {
+    if let Err(e) = tui::run(game) {
+        eprintln!("the terminal stopped working: {}", e);
+        process::exit(1);
+    }
+}
+
+#[cfg(not(feature = "tui"))]
+fn run_tui(_game: Game) {
+    eprintln!("nmm was built without the tui feature, try: cargo run --features tui -- --tui");
+    process::exit(2);
+}
+
+fn prompt(game: &Game) {
+    if game.get_status() == GameStatus::Playing {
+        print!("player {}> ", player_number(game));
+    } else {
+        print!("> ");
+    }
+    io::stdout().flush().expect("couldn't write to the terminal");
+}
+
+fn player_number(game: &Game) -> u8 {
+    match game.get_next_player() {
+        Player::One => 1,
+        Player::Two => 2
+    }
+}
+
+impl Session {
+    // returns false once the player has asked to leave
+    fn run(&mut self, line: &str) -> bool {
+        let mut words = line.split_whitespace();
+        let command = match words.next() {
+            Some(command) => command,
+            None => return true
+        };
+        let argument = words.next();
+
+        match (command, argument) {
+            ("quit", _) | ("exit", _) => return false,
+            ("help", _)         => println!("{}", HELP),
+            ("new", _)          => {
+                self.game = Game::with_variant(self.variant);
+                self.undo.clear();
+                print!("{}", self.game);
+            },
+            ("undo", _)         => match self.undo.pop() {
+                Some(json) => {
+                    self.game = Game::load(&json).expect("couldn't restore the game");
+                    print!("{}", self.game);
+                },
+                None => println!("there's nothing to undo")
+            },
+            ("save", Some(path)) => match fs::write(path, self.game.get_json()) {
+                Ok(_) => println!("saved to {}", path),
+                Err(e) => println!("couldn't save to {}: {}", path, e)
+            },
+            ("load", Some(path)) => match self.load(path) {
+                Ok(_) => print!("{}", self.game),
+                Err(message) => println!("{}", message)
+            },
+            ("save", None) | ("load", None) => println!("{} needs a file name", command),
+            _ => self.play(command)
+        }
+
+        true
+    }
+
+    fn play(&mut self, notation: &str) {
+        let player = self.game.get_next_player();
+        let game_move = match GameMove::from_notation(player, notation) {
+            Some(game_move) => game_move,
+            None => {
+                println!("didn't understand \"{}\", type help for examples", notation);
+                return;
+            }
+        };
+
+        if let Err(error) = self.game.validate(&game_move) {
+            println!("can't play {}: {}", game_move, error);
+            return;
+        }
+
+        self.undo.push(self.game.get_json());
+        self.game.submit(game_move);
+        print!("{}", self.game);
+    }
+
+    fn load(&mut self, path: &str) -> Result<(), String> {
+        let json = fs::read_to_string(path).map_err(|e| format!("couldn't read {}: {}", path, e))?;
+        let game = Game::load(&json).map_err(|e| format!("{} isn't a saved game: {}", path, e))?;
+
+        self.variant = game.get_variant();
+        self.game = game;
+        self.undo.clear();
+        Ok(())
+    }
+}
@@ -0,0 +1,231 @@
+use std::io::{self, Write};
+use std::mem;
+use crossterm::{cursor, execute, queue, style, terminal};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use nmm_lib::{Game, GameMove, GameStatus, Location, MoveError, Player};
+
+const KEYS: &str = "arrows/hjkl move  enter/space choose  esc cancel  u undo  q quit";
+
+enum Stage {
+    // picking a point to place on, or a man to move
+    Choosing,
+    Moving(Location),
+    // the move is chosen and is owed this many more removals
+    Removing(GameMove, usize)
+}
+
+struct Tui {
+    game: Game,
+    cursor: Location,
+    stage: Stage,
+    message: String,
+    undo: Vec<String>
+}
+
+pub fn run(game: Game) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    terminal::enable_raw_mode()?;
+    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+    let mut tui = Tui {
+        game,
+        cursor: Location::D6,
+        stage: Stage::Choosing,
+        message: String::new(),
+        undo: vec!()
+    };
+    let result = tui.run(&mut stdout);
+
+    execute!(stdout, cursor::Show, terminal::LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+    result
+}
+
+impl Tui {
+    fn run(&mut self, stdout: &mut io::Stdout) -> io::Result<()> {
+        loop {
+            self.draw(stdout)?;
+
+            let key = match event::read()? {
+                Event::Key(key) if key.kind != KeyEventKind::Release => key,
+                _ => continue
+            };
+
+            self.message.clear();
+            match key.code {
+                KeyCode::Char('q')  => return Ok(()),
+                KeyCode::Char('u')  => self.undo(),
+                KeyCode::Esc        => match self.stage {
+                    Stage::Choosing => return Ok(()),
+                    _ => self.stage = Stage::Choosing
+                },
+                KeyCode::Enter | KeyCode::Char(' ') => self.choose(),
+                KeyCode::Left | KeyCode::Char('h')  => self.step(-1, 0),
+                KeyCode::Right | KeyCode::Char('l') => self.step(1, 0),
+                KeyCode::Up | KeyCode::Char('k')    => self.step(0, 1),
+                KeyCode::Down | KeyCode::Char('j')  => self.step(0, -1),
+                _ => {}
+            }
+        }
+    }
+
+    // the cursor jumps to the nearest point along the same rank or file
+    fn step(&mut self, files: i8, ranks: i8) {
+        let (file, rank) = Self::get_coordinates(self.cursor);
+        let next = Location::get_points().into_iter().filter(|&location| {
+            let (other_file, other_rank) = Self::get_coordinates(location);
+            let (df, dr) = (other_file - file, other_rank - rank);
+            (files != 0 && dr == 0 && df.signum() == files)
+                || (ranks != 0 && df == 0 && dr.signum() == ranks)
+        }).min_by_key(|&location| {
+            let (other_file, other_rank) = Self::get_coordinates(location);
+            (other_file - file).abs() + (other_rank - rank).abs()
+        });
+
+        if let Some(location) = next {
+            self.cursor = location;
+        }
+    }
+
+    fn choose(&mut self) {
+        if self.game.get_status() != GameStatus::Playing {
+            self.message = String::from("the game is over, u takes back the last move");
+            return;
+        }
+
+        let player = self.game.get_next_player();
+        let owner = self.get_owner(self.cursor);
+
+        match mem::replace(&mut self.stage, Stage::Choosing) {
+            Stage::Choosing if self.has_men_in_hand(player) => {
+                self.play(GameMove::new(player, Location::Hand, self.cursor, None));
+            },
+            Stage::Moving(from) if from == self.cursor => {},
+            Stage::Choosing | Stage::Moving(_) if owner == Some(player) => {
+                self.stage = Stage::Moving(self.cursor);
+            },
+            Stage::Choosing => self.message = String::from("choose one of your men to move"),
+            Stage::Moving(from) => {
+                if !self.play(GameMove::new(player, from, self.cursor, None)) {
+                    self.stage = Stage::Moving(from);
+                }
+            },
+            Stage::Removing(game_move, owed) => {
+                let mut removals = game_move.get_removals();
+                removals.push(self.cursor);
+                let with_removal = GameMove::with_removals(
+                    player,
+                    game_move.get_from(),
+                    game_move.get_to(),
+                    removals
+                );
+
+                match self.game.validate(&with_removal) {
+                    Ok(_) => self.submit(with_removal),
+                    Err(MoveError::MissingRemoval) if owed > 1 => {
+                        self.stage = Stage::Removing(with_removal, owed - 1);
+                    },
+                    Err(error) => {
+                        self.message = format!("can't remove that man: {}", error);
+                        self.stage = Stage::Removing(game_move, owed);
+                    }
+                }
+            }
+        }
+    }
+
+    // false if the move was turned down
+    fn play(&mut self, game_move: GameMove) -> bool {
+        // the mill may earn more men than the opponent has left to take, so the legal moves
+        // say how many are owed
+        let owed = self.game.get_legal_moves().into_iter().filter(|legal| {
+            legal.get_from() == game_move.get_from() && legal.get_to() == game_move.get_to()
+        }).map(|legal| legal.get_removals().len()).max();
+
+        match owed {
+            None => {
+                if let Err(error) = self.game.validate(&game_move) {
+                    self.message = format!("can't play {}: {}", game_move, error);
+                }
+                false
+            },
+            Some(0) => {
+                self.submit(game_move);
+                true
+            },
+            Some(owed) => {
+                self.message = String::from("mill! choose a man to remove");
+                self.stage = Stage::Removing(game_move, owed);
+                true
+            }
+        }
+    }
+
+    fn submit(&mut self, game_move: GameMove) {
+        self.undo.push(self.game.get_json());
+        self.game.submit(game_move);
+        self.stage = Stage::Choosing;
+    }
+
+    fn undo(&mut self) {
+        match self.undo.pop() {
+            Some(json) => {
+                self.game = Game::load(&json).expect("couldn't restore the game");
+                self.stage = Stage::Choosing;
+            },
+            None => self.message = String::from("there's nothing to undo")
+        }
+    }
+
+    fn draw(&self, stdout: &mut io::Stdout) -> io::Result<()> {
+        let selected = match self.stage {
+            Stage::Moving(from) => Some(from),
+            Stage::Removing(ref game_move, _) => Some(game_move.get_to()),
+            Stage::Choosing => None
+        };
+        let drawing = self.game.render_ascii(selected);
+        let lines: Vec<&str> = drawing.lines().collect();
+
+        queue!(stdout, terminal::Clear(terminal::ClearType::All), cursor::MoveTo(0, 0))?;
+        for line in lines.iter() {
+            queue!(stdout, style::Print(line), style::Print("\r\n"))?;
+        }
+        queue!(
+            stdout,
+            style::Print("\r\n"),
+            style::Print(&self.message),
+            style::Print("\r\n"),
+            style::Print(KEYS)
+        )?;
+
+        let (file, rank) = Self::get_coordinates(self.cursor);
+        let (row, col) = (2 * (6 - rank) as u16, 2 + 4 * file as u16);
+        let symbol = lines[row as usize].chars().nth(col as usize).unwrap_or(' ');
+        queue!(
+            stdout,
+            cursor::MoveTo(col, row),
+            style::SetAttribute(style::Attribute::Reverse),
+            style::Print(symbol),
+            style::SetAttribute(style::Attribute::Reset)
+        )?;
+
+        stdout.flush()
+    }
+
+    fn has_men_in_hand(&self, player: Player) -> bool {
+        self.game.get_pieces().iter().any(|piece| {
+            piece.get_player() == player && piece.get_location() == Location::Hand
+        })
+    }
+
+    fn get_owner(&self, location: Location) -> Option<Player> {
+        self.game.get_pieces().iter()
+            .find(|piece| piece.get_location() == location)
+            .map(|piece| piece.get_player())
+    }
+
+    fn get_coordinates(location: Location) -> (i8, i8) {
+        let (file, rank) = location.get_coordinates().unwrap();
+        (file as i8, rank as i8)
+    }
+}
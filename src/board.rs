@@ -0,0 +1,240 @@
+use std::borrow::Cow;
+use serde_json::{self, Value};
+use Location;
+use Location::*;
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Board {
+    points: Cow<'static, [Location]>,
+    edges: Cow<'static, [(Location, Location)]>,
+    mills: Cow<'static, [[Location; 3]]>
+}
+
+const POINTS: [Location; 24] = [
+    A7, A4, A1,
+    B6, B4, B2,
+    C5, C4, C3,
+    D7, D6, D5, D3, D2, D1,
+    E5, E4, E3,
+    F6, F4, F2,
+    G7, G4, G1
+];
+
+const STANDARD_EDGES: [(Location, Location); 32] = [
+    (A7, D7), (D7, G7), (G7, G4), (G4, G1), (G1, D1), (D1, A1), (A1, A4), (A4, A7),
+    (B6, D6), (D6, F6), (F6, F4), (F4, F2), (F2, D2), (D2, B2), (B2, B4), (B4, B6),
+    (C5, D5), (D5, E5), (E5, E4), (E4, E3), (E3, D3), (D3, C3), (C3, C4), (C4, C5),
+    (D7, D6), (D6, D5), (D3, D2), (D2, D1), (A4, B4), (B4, C4), (E4, F4), (F4, G4)
+];
+
+const MORABARABA_EDGES: [(Location, Location); 40] = [
+    (A7, D7), (D7, G7), (G7, G4), (G4, G1), (G1, D1), (D1, A1), (A1, A4), (A4, A7),
+    (B6, D6), (D6, F6), (F6, F4), (F4, F2), (F2, D2), (D2, B2), (B2, B4), (B4, B6),
+    (C5, D5), (D5, E5), (E5, E4), (E4, E3), (E3, D3), (D3, C3), (C3, C4), (C4, C5),
+    (D7, D6), (D6, D5), (D3, D2), (D2, D1), (A4, B4), (B4, C4), (E4, F4), (F4, G4),
+    (A7, B6), (B6, C5), (G7, F6), (F6, E5), (A1, B2), (B2, C3), (G1, F2), (F2, E3)
+];
+
+const STANDARD_MILLS: [[Location; 3]; 16] = [
+    [A7, D7, G7], [B6, D6, F6], [C5, D5, E5], [A4, B4, C4],
+    [E4, F4, G4], [C3, D3, E3], [B2, D2, F2], [A1, D1, G1],
+    [A7, A4, A1], [B6, B4, B2], [C5, C4, C3], [D7, D6, D5],
+    [D3, D2, D1], [E5, E4, E3], [F6, F4, F2], [G7, G4, G1]
+];
+
+const MORABARABA_MILLS: [[Location; 3]; 20] = [
+    [A7, D7, G7], [B6, D6, F6], [C5, D5, E5], [A4, B4, C4],
+    [E4, F4, G4], [C3, D3, E3], [B2, D2, F2], [A1, D1, G1],
+    [A7, A4, A1], [B6, B4, B2], [C5, C4, C3], [D7, D6, D5],
+    [D3, D2, D1], [E5, E4, E3], [F6, F4, F2], [G7, G4, G1],
+    [A7, B6, C5], [G7, F6, E5], [A1, B2, C3], [G1, F2, E3]
+];
+
+pub const STANDARD_BOARD: Board = Board {
+    points: Cow::Borrowed(&POINTS),
+    edges: Cow::Borrowed(&STANDARD_EDGES),
+    mills: Cow::Borrowed(&STANDARD_MILLS)
+};
+
+pub const MORABARABA_BOARD: Board = Board {
+    points: Cow::Borrowed(&POINTS),
+    edges: Cow::Borrowed(&MORABARABA_EDGES),
+    mills: Cow::Borrowed(&MORABARABA_MILLS)
+};
+
+impl Board {
+    pub fn new(
+        points: Vec<Location>,
+        edges: Vec<(Location, Location)>,
+        mills: Vec<[Location; 3]>
+    ) -> Result<Board, String> {
+        if let Some(location) = points.iter().find(|&&location| {
+            location == Hand || location == Captured
+        }) {
+            return Err(format!("{} isn't a point on a board", location.to_str()));
+        }
+
+        let unknown_point = edges.iter().flat_map(|&(a, b)| vec!(a, b))
+            .chain(mills.iter().flat_map(|mill| mill.to_vec()))
+            .find(|location| !points.contains(location));
+
+        if let Some(location) = unknown_point {
+            return Err(format!("{} is used but isn't one of the points", location.to_str()));
+        }
+
+        if let Some(&(a, _)) = edges.iter().find(|&&(a, b)| a == b) {
+            return Err(format!("{} has an edge to itself", a.to_str()));
+        }
+
+        // the mill code takes the other two points of a mill to be two different points
+        if let Some(mill) = mills.iter().find(|mill| {
+            mill[0] == mill[1] || mill[1] == mill[2] || mill[0] == mill[2]
+        }) {
+            let names: Vec<&str> = mill.iter().map(|location| location.to_str()).collect();
+            return Err(format!("the mill {} repeats a point", names.join("-")));
+        }
+
+        Ok(Board {
+            points: Cow::Owned(points),
+            edges: Cow::Owned(edges),
+            mills: Cow::Owned(mills)
+        })
+    }
+
+    // one "key: values" line each for points, edges and mills, e.g.
+    //   points: A7 D7 G7
+    //   edges: A7-D7 D7-G7
+    //   mills: A7-D7-G7
+    pub fn from_text(description: &str) -> Result<Board, String> {
+        let mut points = vec!();
+        let mut edges = vec!();
+        let mut mills = vec!();
+
+        for line in description.lines().map(|line| line.trim()) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut parts = line.splitn(2, ':');
+            let key = parts.next().unwrap().trim();
+            let values = parts.next().ok_or(format!("expected \"{}: ...\"", key))?;
+
+            for value in values.split_whitespace() {
+                let locations = value.split('-')
+                    .map(Self::parse_point)
+                    .collect::<Result<Vec<Location>, String>>()?;
+
+                match (key, locations.len()) {
+                    ("points", 1)   => points.push(locations[0]),
+                    ("edges", 2)    => edges.push((locations[0], locations[1])),
+                    ("mills", 3)    => mills.push([locations[0], locations[1], locations[2]]),
+                    ("points", _) | ("edges", _) | ("mills", _)
+                                    => return Err(format!("unexpected {} \"{}\"", key, value)),
+                    _               => return Err(format!("unknown section \"{}\"", key))
+                }
+            }
+        }
+
+        Board::new(points, edges, mills)
+    }
+
+    pub fn from_json(serialised_board: &str) -> Result<Board, String> {
+        let v: Value = serde_json::from_str(serialised_board).map_err(|e| e.to_string())?;
+        Self::from_value(&v)
+    }
+
+    pub fn get_json(&self) -> String {
+        self.to_value().to_string()
+    }
+
+    pub fn get_points(&self) -> Vec<Location> {
+        self.points.to_vec()
+    }
+
+    pub fn get_edges(&self) -> Vec<(Location, Location)> {
+        self.edges.to_vec()
+    }
+
+    pub fn get_mills(&self) -> Vec<[Location; 3]> {
+        self.mills.to_vec()
+    }
+
+    pub fn contains(&self, location: Location) -> bool {
+        self.points.contains(&location)
+    }
+
+    pub fn is_adjacent(&self, a: Location, b: Location) -> bool {
+        self.edges.iter().any(|&edge| edge == (a, b) || edge == (b, a))
+    }
+
+    pub fn get_neighbors(&self, location: Location) -> Vec<Location> {
+        self.edges.iter().filter_map(|&(a, b)| {
+            if a == location {
+                Some(b)
+            } else if b == location {
+                Some(a)
+            } else {
+                None
+            }
+        }).collect()
+    }
+
+    // the other two points of every mill through the location
+    pub fn get_rows(&self, location: Location) -> Vec<(Location, Location)> {
+        self.mills.iter().filter(|mill| mill.contains(&location)).map(|mill| {
+            let others: Vec<Location> = mill.iter().cloned()
+                .filter(|&point| point != location)
+                .collect();
+            (others[0], others[1])
+        }).collect()
+    }
+
+    pub(crate) fn to_value(&self) -> Value {
+        json!({
+            "points": self.points.iter().map(|location| location.to_str()).collect::<Vec<&str>>(),
+            "edges": self.edges.iter().map(|&(a, b)| {
+                vec!(a.to_str(), b.to_str())
+            }).collect::<Vec<Vec<&str>>>(),
+            "mills": self.mills.iter().map(|mill| {
+                mill.iter().map(|location| location.to_str()).collect()
+            }).collect::<Vec<Vec<&str>>>()
+        })
+    }
+
+    pub(crate) fn from_value(v: &Value) -> Result<Board, String> {
+        let points = Self::unwrap_locations(&v["points"])?;
+        let edges = Self::unwrap_lines(&v["edges"], 2)?.iter()
+            .map(|edge| (edge[0], edge[1]))
+            .collect();
+        let mills = Self::unwrap_lines(&v["mills"], 3)?.iter()
+            .map(|mill| [mill[0], mill[1], mill[2]])
+            .collect();
+
+        Board::new(points, edges, mills)
+    }
+
+    fn unwrap_lines(v: &Value, length: usize) -> Result<Vec<Vec<Location>>, String> {
+        let lines = v.as_array().ok_or(format!("expected a list of {} points", length))?;
+        lines.iter().map(|line| {
+            let locations = Self::unwrap_locations(line)?;
+            if locations.len() != length {
+                return Err(format!("expected {} points in {}", length, line));
+            }
+            Ok(locations)
+        }).collect()
+    }
+
+    fn unwrap_locations(v: &Value) -> Result<Vec<Location>, String> {
+        let locations = v.as_array().ok_or(format!("expected a list of points, found {}", v))?;
+        locations.iter().map(|location| {
+            match location.as_str() {
+                Some(code) => Self::parse_point(code),
+                None => Err(format!("expected a point, found {}", location))
+            }
+        }).collect()
+    }
+
+    fn parse_point(code: &str) -> Result<Location, String> {
+        Location::parse(code).ok_or(format!("unknown point \"{}\"", code))
+    }
+}
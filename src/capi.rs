@@ -0,0 +1,225 @@
+// the C interface described by include/nmm.h, which cbindgen writes from this file:
+//     cbindgen --config cbindgen.toml --output include/nmm.h
+//
+// every function takes a handle made by nmm_game_new or nmm_game_load, and strings are
+// NUL-terminated UTF-8
+
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::ptr;
+use {EndReason, Game, GameMove, GameStatus, MoveError, Player, Variant};
+
+pub struct NmmGame {
+    game: Game,
+    // kept for nmm_game_legal_move, and made again whenever the game changes
+    legal_moves: Vec<CString>
+}
+
+#[repr(C)]
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum NmmResult {
+    Ok = 0,
+    // the move's notation couldn't be read
+    Unreadable,
+    GameOver,
+    NotYourTurn,
+    NoManToMove,
+    StillPlacing,
+    NotOnBoard,
+    Occupied,
+    NotAdjacent,
+    InvalidRemoval,
+    TooManyRemovals,
+    MissingRemoval
+}
+
+#[repr(C)]
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum NmmStatus {
+    Playing = 0,
+    PlayerOneWins,
+    PlayerTwoWins,
+    Draw
+}
+
+#[repr(C)]
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum NmmEndReason {
+    // the game hasn't ended
+    None = 0,
+    MillCapture,
+    Blockade,
+    Resignation,
+    Agreement,
+    Repetition,
+    Timeout
+}
+
+impl NmmGame {
+    fn new(game: Game) -> *mut NmmGame {
+        let mut handle = NmmGame { game, legal_moves: vec!() };
+        handle.refresh();
+        Box::into_raw(Box::new(handle))
+    }
+
+    fn refresh(&mut self) {
+        self.legal_moves = self.game.get_legal_moves().iter().map(|game_move| {
+            CString::new(game_move.to_string()).unwrap()
+        }).collect();
+    }
+}
+
+impl From<MoveError> for NmmResult {
+    fn from(error: MoveError) -> NmmResult {
+        match error {
+            MoveError::GameOver         => NmmResult::GameOver,
+            MoveError::NotYourTurn      => NmmResult::NotYourTurn,
+            MoveError::NoManToMove      => NmmResult::NoManToMove,
+            MoveError::StillPlacing     => NmmResult::StillPlacing,
+            MoveError::NotOnBoard       => NmmResult::NotOnBoard,
+            MoveError::Occupied         => NmmResult::Occupied,
+            MoveError::NotAdjacent      => NmmResult::NotAdjacent,
+            MoveError::InvalidRemoval   => NmmResult::InvalidRemoval,
+            MoveError::TooManyRemovals  => NmmResult::TooManyRemovals,
+            MoveError::MissingRemoval   => NmmResult::MissingRemoval
+        }
+    }
+}
+
+/// A new game, of Morabaraba if `morabaraba` isn't 0. Free it with `nmm_game_free`.
+#[no_mangle]
+pub extern "C" fn nmm_game_new(morabaraba: i32) -> *mut NmmGame {
+    let variant = if morabaraba != 0 { Variant::Morabaraba } else { Variant::NineMensMorris };
+    NmmGame::new(Game::with_variant(variant))
+}
+
+/// A game saved by `nmm_game_get_json`, or NULL if `json` isn't one.
+///
+/// # Safety
+/// `json` must be a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn nmm_game_load(json: *const c_char) -> *mut NmmGame {
+    let game = match read_string(json).map(Game::load) {
+        Some(Ok(game)) => game,
+        _ => return ptr::null_mut()
+    };
+    NmmGame::new(game)
+}
+
+/// # Safety
+/// `game` must be NULL or a handle that hasn't already been freed.
+#[no_mangle]
+pub unsafe extern "C" fn nmm_game_free(game: *mut NmmGame) {
+    if !game.is_null() {
+        drop(Box::from_raw(game));
+    }
+}
+
+/// Plays a move for the player to move, written like "a7", "a7-a4" or "a7-a4xd2".
+///
+/// # Safety
+/// `game` must be a live handle and `notation` a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn nmm_game_submit(game: *mut NmmGame, notation: *const c_char) -> NmmResult {
+    let handle = &mut *game;
+    let player = handle.game.get_next_player();
+    let game_move = match read_string(notation).and_then(|notation| GameMove::from_notation(player, notation)) {
+        Some(game_move) => game_move,
+        None => return NmmResult::Unreadable
+    };
+
+    if let Err(error) = handle.game.validate(&game_move) {
+        return error.into();
+    }
+
+    handle.game.submit(game_move);
+    handle.refresh();
+    NmmResult::Ok
+}
+
+/// # Safety
+/// `game` must be a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn nmm_game_legal_move_count(game: *const NmmGame) -> usize {
+    let handle = &*game;
+    handle.legal_moves.len()
+}
+
+/// The legal move at `index`, or NULL past the end. The string belongs to the game, and
+/// lasts until the next move is submitted or the game is freed.
+///
+/// # Safety
+/// `game` must be a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn nmm_game_legal_move(game: *const NmmGame, index: usize) -> *const c_char {
+    let handle = &*game;
+    match handle.legal_moves.get(index) {
+        Some(notation) => notation.as_ptr(),
+        None => ptr::null()
+    }
+}
+
+/// The game as JSON, for `nmm_game_load`. Free it with `nmm_string_free`.
+///
+/// # Safety
+/// `game` must be a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn nmm_game_get_json(game: *const NmmGame) -> *mut c_char {
+    CString::new((*game).game.get_json()).unwrap().into_raw()
+}
+
+/// # Safety
+/// `string` must be NULL or a string returned by `nmm_game_get_json`.
+#[no_mangle]
+pub unsafe extern "C" fn nmm_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+/// 1 or 2.
+///
+/// # Safety
+/// `game` must be a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn nmm_game_next_player(game: *const NmmGame) -> i32 {
+    Game::wrap_player((*game).game.get_next_player()) as i32
+}
+
+/// # Safety
+/// `game` must be a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn nmm_game_status(game: *const NmmGame) -> NmmStatus {
+    match (*game).game.get_status() {
+        GameStatus::Playing                 => NmmStatus::Playing,
+        GameStatus::Win(Player::One, _)     => NmmStatus::PlayerOneWins,
+        GameStatus::Win(Player::Two, _)     => NmmStatus::PlayerTwoWins,
+        GameStatus::Draw(_)                 => NmmStatus::Draw
+    }
+}
+
+/// # Safety
+/// `game` must be a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn nmm_game_end_reason(game: *const NmmGame) -> NmmEndReason {
+    let reason = match (*game).game.get_status() {
+        GameStatus::Playing => return NmmEndReason::None,
+        GameStatus::Win(_, reason) | GameStatus::Draw(reason) => reason
+    };
+
+    match reason {
+        EndReason::MillCapture  => NmmEndReason::MillCapture,
+        EndReason::Blockade     => NmmEndReason::Blockade,
+        EndReason::Resignation  => NmmEndReason::Resignation,
+        EndReason::Agreement    => NmmEndReason::Agreement,
+        EndReason::Repetition   => NmmEndReason::Repetition,
+        EndReason::Timeout      => NmmEndReason::Timeout
+    }
+}
+
+unsafe fn read_string<'a>(string: *const c_char) -> Option<&'a str> {
+    if string.is_null() {
+        return None;
+    }
+    CStr::from_ptr(string).to_str().ok()
+}
@@ -0,0 +1,168 @@
+use std::cmp;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use Player;
+
+// anything that can say how much time has passed since some fixed moment
+pub trait TimeSource: Send + Sync {
+    fn now(&self) -> Duration;
+}
+
+pub struct SystemTimeSource {
+    start: Instant
+}
+
+impl SystemTimeSource {
+    pub fn new() -> SystemTimeSource {
+        SystemTimeSource {
+            start: Instant::now()
+        }
+    }
+}
+
+impl Default for SystemTimeSource {
+    fn default() -> SystemTimeSource {
+        SystemTimeSource::new()
+    }
+}
+
+impl TimeSource for SystemTimeSource {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum TimeControl {
+    SuddenDeath(Duration),
+    // base time, and the increment added after each move
+    Fischer(Duration, Duration),
+    // base time, and the most of the time spent on a move that's given back after it
+    Bronstein(Duration, Duration)
+}
+
+impl TimeControl {
+    pub fn get_base(&self) -> Duration {
+        match *self {
+            TimeControl::SuddenDeath(base)
+                | TimeControl::Fischer(base, _)
+                | TimeControl::Bronstein(base, _) => base
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Clock {
+    control: TimeControl,
+    remaining: [Duration; 2],
+    move_times: Vec<Duration>,
+    turn_started: Duration,
+    // time already spent on this turn when the clock was saved
+    carried_over: Duration,
+    source: Arc<dyn TimeSource>
+}
+
+impl PartialEq for Clock {
+    fn eq(&self, other: &Clock) -> bool {
+        self.control == other.control
+            && self.remaining == other.remaining
+            && self.move_times == other.move_times
+    }
+}
+
+impl fmt::Debug for Clock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Clock")
+            .field("control", &self.control)
+            .field("remaining", &self.remaining)
+            .field("move_times", &self.move_times)
+            .finish()
+    }
+}
+
+impl Clock {
+    pub fn new(control: TimeControl) -> Clock {
+        Self::with_time_source(control, Arc::new(SystemTimeSource::new()))
+    }
+
+    // the clock starts running for whoever is to move as soon as it's made
+    pub fn with_time_source(control: TimeControl, source: Arc<dyn TimeSource>) -> Clock {
+        Clock {
+            control,
+            remaining: [control.get_base(); 2],
+            move_times: vec!(),
+            turn_started: source.now(),
+            carried_over: Duration::default(),
+            source
+        }
+    }
+
+    pub fn get_control(&self) -> TimeControl {
+        self.control
+    }
+
+    // time spent on each move so far, in the order they were played
+    pub fn get_move_times(&self) -> Vec<Duration> {
+        self.move_times.clone()
+    }
+
+    pub fn get_time_left(&self, player: Player, on_move: bool) -> Duration {
+        let remaining = self.remaining[Self::index(player)];
+        if !on_move {
+            return remaining;
+        }
+
+        remaining.checked_sub(self.get_turn_elapsed()).unwrap_or_default()
+    }
+
+    // how long the player to move has been thinking
+    pub fn get_turn_elapsed(&self) -> Duration {
+        self.carried_over + self.source.now().checked_sub(self.turn_started).unwrap_or_default()
+    }
+
+    // time the old source measured on the running turn is dropped, but not time carried
+    // over from a save
+    pub fn set_time_source(&mut self, source: Arc<dyn TimeSource>) {
+        self.turn_started = source.now();
+        self.source = source;
+    }
+
+    pub(crate) fn restore(
+        control: TimeControl,
+        remaining: [Duration; 2],
+        move_times: Vec<Duration>,
+        turn_elapsed: Duration
+    ) -> Clock {
+        let mut clock = Self::new(control);
+        clock.remaining = remaining;
+        clock.move_times = move_times;
+        clock.carried_over = turn_elapsed;
+        clock
+    }
+
+    pub(crate) fn record(&mut self, player: Player) {
+        let elapsed = self.get_turn_elapsed();
+        let time_left = self.get_time_left(player, true);
+
+        self.remaining[Self::index(player)] = match self.control {
+            TimeControl::Fischer(_, increment) if time_left > Duration::default() => {
+                time_left + increment
+            },
+            TimeControl::Bronstein(_, delay) if time_left > Duration::default() => {
+                time_left + cmp::min(delay, elapsed)
+            },
+            _ => time_left
+        };
+        self.move_times.push(elapsed);
+        self.turn_started = self.source.now();
+        self.carried_over = Duration::default();
+    }
+
+    fn index(player: Player) -> usize {
+        match player {
+            Player::One => 0,
+            Player::Two => 1
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use Game;
+use GameMove;
+use Location;
+use Phase;
+use Piece;
+use Player;
+use RuleSet;
+
+// own, opponent and empty planes over the 24 points, then men in hand for each side,
+// the placing and flying flags, and whether player two is to move
+pub const FEATURE_COUNT: usize = 3 * 24 + 2 + 3 + 1;
+
+// from (hand or a point) × to (a point) × removal (none or a point)
+pub const ACTION_COUNT: usize = 25 * 24 * 25;
+
+const POINT_COUNT: usize = 24;
+
+// one of the 16 ways the board maps onto itself: the 8 rotations and reflections of the
+// square, each with or without swapping the outer and inner rings.
+// Only meaningful for the standard and Morabaraba boards.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct Symmetry {
+    quarter_turns: u8,
+    mirrored: bool,
+    rings_swapped: bool
+}
+
+impl Symmetry {
+    pub fn identity() -> Symmetry {
+        Symmetry { quarter_turns: 0, mirrored: false, rings_swapped: false }
+    }
+
+    // identity first
+    pub fn all() -> Vec<Symmetry> {
+        let mut symmetries = vec!();
+        for &rings_swapped in [false, true].iter() {
+            for &mirrored in [false, true].iter() {
+                for quarter_turns in 0..4 {
+                    symmetries.push(Symmetry { quarter_turns, mirrored, rings_swapped });
+                }
+            }
+        }
+        symmetries
+    }
+
+    // the hand and captured men stay where they are
+    pub fn apply(&self, location: Location) -> Location {
+        let (file, rank) = match location.get_coordinates() {
+            Some(coordinates) => coordinates,
+            None => return location
+        };
+        let (mut x, mut y) = (file as i8 - 3, rank as i8 - 3);
+
+        if self.rings_swapped {
+            let ring = x.abs().max(y.abs());
+            let other_ring = 4 - ring;
+            x = x / ring * other_ring;
+            y = y / ring * other_ring;
+        }
+        if self.mirrored {
+            x = -x;
+        }
+        for _ in 0..self.quarter_turns {
+            let turned = (y, -x);
+            x = turned.0;
+            y = turned.1;
+        }
+
+        Location::get_points().into_iter().find(|point| {
+            point.get_coordinates() == Some(((x + 3) as u8, (y + 3) as u8))
+        }).unwrap()
+    }
+
+    // the symmetry that undoes this one
+    pub fn inverse(&self) -> Symmetry {
+        Symmetry::all().into_iter().find(|other| {
+            Location::get_points().into_iter().all(|point| other.apply(self.apply(point)) == point)
+        }).unwrap()
+    }
+
+    pub fn apply_to_move(&self, game_move: &GameMove) -> GameMove {
+        GameMove::with_removals(
+            game_move.get_player(),
+            self.apply(game_move.get_from()),
+            self.apply(game_move.get_to()),
+            game_move.get_removals().into_iter().map(|location| self.apply(location)).collect()
+        )
+    }
+
+    pub fn apply_to_action(&self, player: Player, index: usize) -> Option<usize> {
+        let game_move = GameMove::from_action_index(player, index)?;
+        self.apply_to_move(&game_move).get_action_index()
+    }
+
+    pub fn apply_to_features(&self, features: &[f32]) -> Vec<f32> {
+        let mut transformed = features.to_vec();
+        for (i, &point) in Location::get_points().iter().enumerate() {
+            let j = point_index(self.apply(point)).unwrap();
+            for plane in 0..3 {
+                transformed[plane * POINT_COUNT + j] = features[plane * POINT_COUNT + i];
+            }
+        }
+        transformed
+    }
+}
+
+impl Game {
+    // the position as seen by the player to move, FEATURE_COUNT values long
+    pub fn encode_features(&self) -> Vec<f32> {
+        let own = self.next_player;
+        let opponent = Self::switch_player(own);
+        let pieces = self.get_pieces();
+        let owner = |location: Location| {
+            pieces.iter().find(|piece| piece.get_location() == location).map(|piece| piece.get_player())
+        };
+        let in_hand = |player: Player| {
+            pieces.iter().filter(|piece| {
+                piece.get_player() == player && piece.get_location() == Location::Hand
+            }).count() as f32
+        };
+        let flag = |set: bool| if set { 1.0 } else { 0.0 };
+
+        let mut features = vec!(0.0; FEATURE_COUNT);
+        for (i, &point) in Location::get_points().iter().enumerate() {
+            let plane = match owner(point) {
+                Some(player) if player == own => 0,
+                Some(_) => 1,
+                None => 2
+            };
+            features[plane * POINT_COUNT + i] = 1.0;
+        }
+
+        features[72] = in_hand(own);
+        features[73] = in_hand(opponent);
+        features[74] = flag(self.get_phase(own) == Phase::Placing);
+        features[75] = flag(self.get_phase(own) == Phase::Flying);
+        features[76] = flag(self.get_phase(opponent) == Phase::Flying);
+        features[77] = flag(own == Player::Two);
+        features
+    }
+
+    // the position encode_features described, played under the given rules;
+    // the history and clock aren't part of the encoding
+    pub fn decode_features(features: &[f32], rules: RuleSet) -> Option<Game> {
+        if features.len() != FEATURE_COUNT {
+            return None;
+        }
+
+        let own = if features[77] > 0.5 { Player::Two } else { Player::One };
+        let opponent = Self::switch_player(own);
+        let count = rules.variant.get_piece_count();
+        let mut pieces = vec!();
+
+        for &(player, plane, hand) in [(own, 0, 72), (opponent, 1, 73)].iter() {
+            let on_board: Vec<Location> = Location::get_points().into_iter().enumerate()
+                .filter(|&(i, _)| features[plane * POINT_COUNT + i] > 0.5)
+                .map(|(_, point)| point)
+                .collect();
+            let in_hand = features[hand].round() as usize;
+            let captured = count.checked_sub(on_board.len() + in_hand)?;
+
+            pieces.extend(on_board.into_iter().map(|location| Piece::new(player, location)));
+            pieces.extend(vec!(Piece::new(player, Location::Hand); in_hand));
+            pieces.extend(vec!(Piece::new(player, Location::Captured); captured));
+        }
+
+        let mut game = Game::with_rules(rules);
+        game.pieces = pieces;
+        game.next_player = own;
+        Some(game)
+    }
+}
+
+impl GameMove {
+    // None for moves off the board, or with more than one removal
+    pub fn get_action_index(&self) -> Option<usize> {
+        let from = match self.get_from() {
+            Location::Hand => 0,
+            location => point_index(location)? + 1
+        };
+        let to = point_index(self.get_to())?;
+        let removals = self.get_removals();
+        let removal = match removals.len() {
+            0 => 0,
+            1 => point_index(removals[0])? + 1,
+            _ => return None
+        };
+
+        Some((from * POINT_COUNT + to) * (POINT_COUNT + 1) + removal)
+    }
+
+    pub fn from_action_index(player: Player, index: usize) -> Option<GameMove> {
+        if index >= ACTION_COUNT {
+            return None;
+        }
+
+        let points = Location::get_points();
+        let removal = index % (POINT_COUNT + 1);
+        let to = index / (POINT_COUNT + 1) % POINT_COUNT;
+        let from = index / (POINT_COUNT + 1) / POINT_COUNT;
+
+        let from = if from == 0 { Location::Hand } else { points[from - 1] };
+        let removal = if removal == 0 { None } else { Some(points[removal - 1]) };
+        Some(GameMove::new(player, from, points[to], removal))
+    }
+}
+
+fn point_index(location: Location) -> Option<usize> {
+    Location::get_points().iter().position(|&point| point == location)
+}
@@ -0,0 +1,62 @@
+use serde_json::{self, Value, Error};
+use Game;
+use invalid;
+use GameMove;
+use Player;
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum GameAction {
+    Move(GameMove),
+    Resign(Player),
+    OfferDraw(Player),
+    AcceptDraw(Player),
+    DeclineDraw(Player)
+}
+
+impl GameAction {
+    pub fn load(serialised_action: &str) -> Result<GameAction, Error> {
+        let v: Value = serde_json::from_str(serialised_action)?;
+        let player = || Game::unwrap_player_value(&v["player"]);
+
+        Ok(match v["action"].as_str() {
+            Some("Move")        => GameAction::Move(GameMove::from_value(&v["move"])?),
+            Some("Resign")      => GameAction::Resign(player()?),
+            Some("OfferDraw")   => GameAction::OfferDraw(player()?),
+            Some("AcceptDraw")  => GameAction::AcceptDraw(player()?),
+            Some("DeclineDraw") => GameAction::DeclineDraw(player()?),
+            _                   => return Err(invalid(format!("unknown action {}", v["action"])))
+        })
+    }
+
+    pub fn get_json(&self) -> String {
+        let json = match *self {
+            GameAction::Move(ref game_move) => json!({
+                "action": "Move",
+                "move": game_move.to_value()
+            }),
+            GameAction::Resign(player) => Self::wrap("Resign", player),
+            GameAction::OfferDraw(player) => Self::wrap("OfferDraw", player),
+            GameAction::AcceptDraw(player) => Self::wrap("AcceptDraw", player),
+            GameAction::DeclineDraw(player) => Self::wrap("DeclineDraw", player)
+        };
+
+        json.to_string()
+    }
+
+    pub fn get_player(&self) -> Player {
+        match *self {
+            GameAction::Move(ref game_move) => game_move.get_player(),
+            GameAction::Resign(player)
+                | GameAction::OfferDraw(player)
+                | GameAction::AcceptDraw(player)
+                | GameAction::DeclineDraw(player) => player
+        }
+    }
+
+    fn wrap(action: &str, player: Player) -> Value {
+        json!({
+            "action": action,
+            "player": Game::wrap_player(player)
+        })
+    }
+}
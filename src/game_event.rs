@@ -0,0 +1,73 @@
+use std::fmt;
+use std::sync::Arc;
+use GameStatus;
+use Location;
+use Player;
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Phase {
+    Placing,
+    Moving,
+    Flying
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum GameEvent {
+    PiecePlaced(Player, Location),
+    // player, from, to
+    PieceMoved(Player, Location, Location),
+    MillFormed(Player, [Location; 3]),
+    // the owner of the captured man, and where it was taken from
+    PieceCaptured(Player, Location),
+    PhaseChanged(Player, Phase),
+    TurnChanged(Player),
+    GameOver(GameStatus)
+}
+
+// told about everything that happens to a game, in the order it happens
+pub trait GameListener: Send + Sync {
+    fn notify(&self, event: &GameEvent);
+}
+
+impl<F> GameListener for F where F: Fn(&GameEvent) + Send + Sync {
+    fn notify(&self, event: &GameEvent) {
+        self(event)
+    }
+}
+
+// listeners aren't part of a game's state, so two games are equal whoever is listening,
+// and a copy of a game starts with nobody listening to it
+#[derive(Default)]
+pub(crate) struct Listeners(Vec<Arc<dyn GameListener>>);
+
+impl Listeners {
+    pub(crate) fn add(&mut self, listener: Arc<dyn GameListener>) {
+        self.0.push(listener);
+    }
+
+    pub(crate) fn notify_all(&self, events: &[GameEvent]) {
+        for event in events.iter() {
+            for listener in self.0.iter() {
+                listener.notify(event);
+            }
+        }
+    }
+}
+
+impl Clone for Listeners {
+    fn clone(&self) -> Listeners {
+        Listeners::default()
+    }
+}
+
+impl PartialEq for Listeners {
+    fn eq(&self, _: &Listeners) -> bool {
+        true
+    }
+}
+
+impl fmt::Debug for Listeners {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} listeners", self.0.len())
+    }
+}
@@ -1,3 +1,6 @@
+use std::fmt;
+use serde_json::{Value, Error};
+use Game;
 use Player;
 use Location;
 
@@ -5,8 +8,10 @@ use Location;
 macro_rules! game_move {
     ($player:ident, $from:ident, $to:ident) 
         => (GameMove::new(Player::$player, Location::$from, Location::$to, None));
-    ($player:ident, $from:ident, $to:ident, $remove:ident)
-        => (GameMove::new(Player::$player, Location::$from, Location::$to, Some(Location::$remove)));
+    ($player:ident, $from:ident, $to:ident, $($remove:ident),+)
+        => (GameMove::with_removals(
+            Player::$player, Location::$from, Location::$to, vec!($(Location::$remove),+)
+        ));
 }
 
 #[derive(PartialEq, Debug, Clone)]
@@ -14,16 +19,43 @@ pub struct GameMove {
     player: Player,
     from: Location,
     to: Location,
-    remove: Option<Location>
+    removals: Vec<Location>
 }
 
 impl GameMove {
     pub fn new(player: Player, from: Location, to: Location, remove: Option<Location>) -> GameMove {
+        Self::with_removals(player, from, to, remove.into_iter().collect())
+    }
+
+    pub fn with_removals(
+        player: Player,
+        from: Location,
+        to: Location,
+        removals: Vec<Location>
+    ) -> GameMove {
         GameMove {
             player,
             from,
             to,
-            remove
+            removals
+        }
+    }
+
+    // "a7" places a man, "a7-a4" moves one, and each "xd2" that follows removes one
+    pub fn from_notation(player: Player, notation: &str) -> Option<GameMove> {
+        let mut parts = notation.trim().split(['x', 'X']);
+        let points: Vec<&str> = parts.next().unwrap().split('-').collect();
+        let removals = parts.map(Location::parse).collect::<Option<Vec<Location>>>()?;
+
+        match points.len() {
+            1 => Some(Self::with_removals(player, Location::Hand, Location::parse(points[0])?, removals)),
+            2 => Some(Self::with_removals(
+                player,
+                Location::parse(points[0])?,
+                Location::parse(points[1])?,
+                removals
+            )),
+            _ => None
         }
     }
 
@@ -40,6 +72,52 @@ impl GameMove {
     }
 
     pub fn get_remove(&self) -> Option<Location> {
-        self.remove
+        self.removals.first().cloned()
+    }
+
+    pub fn get_removals(&self) -> Vec<Location> {
+        self.removals.clone()
+    }
+
+    pub(crate) fn to_value(&self) -> Value {
+        json!({
+            "player": Game::wrap_player(self.player),
+            "from": self.from.to_str(),
+            "to": self.to.to_str(),
+            "removals": self.removals.iter().map(|location| {
+                location.to_str()
+            }).collect::<Vec<&str>>()
+        })
+    }
+
+    pub(crate) fn from_value(v: &Value) -> Result<GameMove, Error> {
+        // saves from before a move could take more than one man have a single "remove", or null
+        let removals = match v["removals"].as_array() {
+            Some(removals) => removals.iter().map(Game::unwrap_location).collect::<Result<_, _>>()?,
+            None if v["remove"].is_null() => vec!(),
+            None => vec!(Game::unwrap_location(&v["remove"])?)
+        };
+
+        Ok(GameMove::with_removals(
+            Game::unwrap_player_value(&v["player"])?,
+            Game::unwrap_location(&v["from"])?,
+            Game::unwrap_location(&v["to"])?,
+            removals
+        ))
+    }
+}
+
+impl fmt::Display for GameMove {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.from != Location::Hand {
+            write!(f, "{}-", self.from.to_str().to_lowercase())?;
+        }
+        write!(f, "{}", self.to.to_str().to_lowercase())?;
+
+        for location in self.removals.iter() {
+            write!(f, "x{}", location.to_str().to_lowercase())?;
+        }
+
+        Ok(())
     }
 }
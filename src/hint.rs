@@ -0,0 +1,99 @@
+use Game;
+use GameMove;
+use Location;
+use Player;
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum HintReason {
+    // the mill the move completes
+    ClosesMill([Location; 3]),
+    // the opponent's mill the move stops
+    BlocksMill([Location; 3]),
+    // the move leaves two mills to close, and only one can be blocked
+    SetsUpDoubleMill([Location; 3], [Location; 3]),
+    // the player had almost no moves left, and this one frees some men
+    EscapesBlockade,
+    // nothing better, so the move that gives the opponent the fewest chances
+    SafestMove
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Hint {
+    game_move: GameMove,
+    reason: HintReason
+}
+
+impl Hint {
+    pub fn get_move(&self) -> GameMove {
+        self.game_move.clone()
+    }
+
+    pub fn get_reason(&self) -> HintReason {
+        self.reason
+    }
+}
+
+impl Game {
+    // a suggestion for the player to move, or None if they have no moves
+    pub fn hint(&self) -> Option<Hint> {
+        let player = self.next_player;
+        let opponent = Self::switch_player(player);
+        let moves = self.get_legal_moves();
+        let hint = |game_move: &GameMove, reason| Some(Hint { game_move: game_move.clone(), reason });
+
+        // taking the man that leaves the opponent the fewest mills to close
+        let closing = moves.iter().filter(|game_move| !game_move.get_removals().is_empty());
+        if let Some(game_move) = closing.min_by_key(|game_move| self.count_threats_after(game_move, opponent)) {
+            let to = game_move.get_to();
+            let (a, b) = self.get_rows(to).into_iter().find(|&(a, b)| {
+                a != game_move.get_from() && b != game_move.get_from()
+                    && self.does_piece_exist(a, player) && self.does_piece_exist(b, player)
+            })?;
+            return hint(game_move, HintReason::ClosesMill(self.get_mill(&[a, to, b])?));
+        }
+
+        for game_move in moves.iter() {
+            if let Some(mill) = self.get_mill_closed(opponent, game_move.get_to()) {
+                return hint(game_move, HintReason::BlocksMill(mill));
+            }
+        }
+
+        for game_move in moves.iter() {
+            let after = self.play_out(game_move);
+            let threats = after.get_mill_threats(player);
+            if threats.len() > 1 && after.get_mill_threats(opponent).is_empty() {
+                return hint(game_move, HintReason::SetsUpDoubleMill(threats[0], threats[1]));
+            }
+        }
+
+        let safest = moves.iter().min_by_key(|game_move| self.count_threats_after(game_move, opponent))?;
+        let sliding = !self.is_in_placement_phase() && !self.is_flying(player);
+        if sliding && self.get_mobility(player) <= 2 {
+            let freest = moves.iter().max_by_key(|game_move| self.play_out(game_move).get_mobility(player))?;
+            if self.play_out(freest).get_mobility(player) > self.get_mobility(player) {
+                return hint(freest, HintReason::EscapesBlockade);
+            }
+        }
+
+        hint(safest, HintReason::SafestMove)
+    }
+
+    // how many of the player's men could slide somewhere
+    fn get_mobility(&self, player: Player) -> usize {
+        self.pieces.iter().filter(|piece| {
+            piece.get_player() == player && self.board.get_neighbors(piece.get_location()).iter().any(|&point| {
+                !self.is_location_occupied(point)
+            })
+        }).count()
+    }
+
+    fn count_threats_after(&self, game_move: &GameMove, player: Player) -> usize {
+        self.play_out(game_move).get_mill_threats(player).len()
+    }
+
+    fn play_out(&self, game_move: &GameMove) -> Game {
+        let mut after = self.clone();
+        after.submit(game_move.clone());
+        after
+    }
+}
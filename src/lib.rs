@@ -1,119 +1,550 @@
+extern crate serde;
 #[macro_use]
 extern crate serde_json;
+#[cfg(feature = "wasm")]
+extern crate wasm_bindgen;
 
 #[macro_use]
 mod piece;
 #[macro_use]
 mod game_move;
+mod game_action;
+mod game_event;
 mod location;
 mod player;
+mod variant;
+mod rule_set;
+mod board;
+mod move_error;
+mod clock;
+mod ascii;
+mod encoding;
+mod opening_book;
+mod hint;
+mod threats;
+pub mod svg;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "capi")]
+pub mod capi;
 
 #[cfg(test)]
 mod test;
 
+use std::cmp;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
 use serde_json::{Value, Error};
+use game_event::Listeners;
 pub use piece::Piece;
 pub use game_move::GameMove;
+pub use game_action::GameAction;
+pub use game_event::{GameEvent, GameListener, Phase};
 pub use location::Location;
 pub use player::Player;
+pub use variant::Variant;
+pub use rule_set::{RuleSet, BlockedOutcome};
+pub use board::{Board, STANDARD_BOARD, MORABARABA_BOARD};
+pub use move_error::MoveError;
+pub use clock::{Clock, TimeControl, TimeSource, SystemTimeSource};
+pub use encoding::{Symmetry, FEATURE_COUNT, ACTION_COUNT};
+pub use opening_book::OpeningBook;
+pub use hint::{Hint, HintReason};
+pub use threats::Threat;
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone, Copy)]
 pub enum GameStatus {
     Playing,
-    Win(Player),
-    Draw
+    Win(Player, EndReason),
+    Draw(EndReason)
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum EndReason {
+    // the loser was reduced to two men
+    MillCapture,
+    Blockade,
+    Resignation,
+    Agreement,
+    Repetition,
+    Timeout
+}
+
+impl FromStr for EndReason {
+    type Err = String;
+
+    fn from_str(code: &str) -> Result<EndReason, String> {
+        use EndReason::*;
+        match code {
+            "MillCapture"   => Ok(MillCapture),
+            "Blockade"      => Ok(Blockade),
+            "Resignation"   => Ok(Resignation),
+            "Agreement"     => Ok(Agreement),
+            "Repetition"    => Ok(Repetition),
+            "Timeout"       => Ok(Timeout),
+            _               => Err(format!("unknown end reason \"{}\"", code))
+        }
+    }
+}
+
+impl EndReason {
+    pub fn to_str(&self) -> &'static str {
+        use EndReason::*;
+        match *self {
+            MillCapture => "MillCapture",
+            Blockade    => "Blockade",
+            Resignation => "Resignation",
+            Agreement   => "Agreement",
+            Repetition  => "Repetition",
+            Timeout     => "Timeout"
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
 pub struct Game{
     pieces: Vec<Piece>,
-    turn: u8,
-    next_player: Player
+    // plies played; long games, such as random playouts, run well past 255
+    turn: u32,
+    next_player: Player,
+    rules: RuleSet,
+    board: Board,
+    history: Vec<GameMove>,
+    clock: Option<Clock>,
+    draw_offer: Option<Player>,
+    // set when the players end the game themselves
+    result: Option<GameStatus>,
+    listeners: Listeners
+}
+
+impl Default for Game {
+    fn default() -> Game {
+        Game::new()
+    }
 }
 
 impl Game {
     pub fn new() -> Game {
+        Self::with_variant(Variant::NineMensMorris)
+    }
+
+    pub fn with_variant(variant: Variant) -> Game {
+        Self::with_rules(RuleSet::for_variant(variant))
+    }
+
+    pub fn with_rules(rules: RuleSet) -> Game {
+        Self::with_board(rules, rules.variant.get_board())
+    }
+
+    pub fn with_board(rules: RuleSet, board: Board) -> Game {
         Game{
-            pieces: Self::get_new_pieces(),
+            pieces: Self::get_new_pieces(rules.variant),
             turn: 0,
-            next_player: Player::One
+            next_player: Player::One,
+            rules,
+            board,
+            history: vec!(),
+            clock: None,
+            draw_offer: None,
+            result: None,
+            listeners: Listeners::default()
         }
     }
 
     pub fn load(serialised_game: &str) -> Result<Game, Error> {
         let v: Value = serde_json::from_str(serialised_game)?;
-        let pieces = Self::unwrap_pieces(v["pieces"].clone());
+        let pieces = Self::unwrap_pieces(&v["pieces"])?;
+        let turn = v["turn"].as_u64().ok_or_else(|| invalid(format!("expected a turn, found {}", v["turn"])))?;
+        let mut rules = v["rules"].clone();
+        // saves from before house rules kept the variant at the top level
+        if rules["variant"].is_null() && v["variant"].is_string() {
+            rules["variant"] = v["variant"].clone();
+        }
+        let rules = Self::unwrap_rules(rules)?;
+        let board = match v.get("board") {
+            Some(board) => Board::from_value(board).map_err(invalid)?,
+            None => rules.variant.get_board()
+        };
+
         Ok(Game{
-            pieces: pieces,
-            turn: v["turn"].as_u64().unwrap() as u8,
-            next_player: Self::unwrap_player(v["player_turn"].as_u64().unwrap())
+            pieces,
+            turn: turn as u32,
+            next_player: Self::unwrap_player_value(&v["player_turn"])?,
+            rules,
+            board,
+            history: Self::unwrap_history(v["history"].clone())?,
+            clock: Self::unwrap_clock(&v["clock"])?,
+            draw_offer: v["draw_offer"].as_u64().map(Self::unwrap_player),
+            result: Self::unwrap_result(&v["result"])?,
+            listeners: Listeners::default()
         })
     }
 
     pub fn get_json(&self) -> String {
         let pieces = self.wrap_pieces();
-        let json = json!({
+        let mut json = json!({
             "pieces": pieces,
             "turn": self.turn,
-            "player_turn": Self::wrap_player(self.next_player)
+            "player_turn": Self::wrap_player(self.next_player),
+            "rules": self.wrap_rules(),
+            "history": self.wrap_history()
         });
+
+        // the variant implies its own board, so only custom boards are written out
+        if self.board != self.rules.variant.get_board() {
+            json["board"] = self.board.to_value();
+        }
+
+        if let Some(ref clock) = self.clock {
+            json["clock"] = Self::wrap_clock(clock);
+        }
+
+        if let Some(player) = self.draw_offer {
+            json["draw_offer"] = json!(Self::wrap_player(player));
+        }
+
+        if let Some(result) = self.result {
+            json["result"] = Self::wrap_result(result);
+        }
         
-        String::from(json.to_string())
+        json.to_string()
     }
 
     pub fn get_pieces(&self) -> Vec<Piece> {
-        return self.pieces.clone();
+        self.pieces.clone()
     }
 
-    pub fn get_turn(&self) -> u8 {
-        return self.turn;
+    pub fn get_turn(&self) -> u32 {
+        self.turn
     }
 
     pub fn get_next_player(&self) -> Player {
-        return self.next_player.clone();
+        self.next_player
+    }
+
+    pub fn get_variant(&self) -> Variant {
+        self.rules.variant
+    }
+
+    pub fn get_rules(&self) -> RuleSet {
+        self.rules
+    }
+
+    pub fn get_board(&self) -> Board {
+        self.board.clone()
+    }
+
+    pub fn get_history(&self) -> Vec<GameMove> {
+        self.history.clone()
+    }
+
+    pub fn get_clock(&self) -> Option<&Clock> {
+        self.clock.as_ref()
+    }
+
+    pub fn set_clock(&mut self, clock: Clock) {
+        self.clock = Some(clock);
+    }
+
+    // a loaded game's clock uses the system time until told otherwise
+    pub fn set_time_source(&mut self, source: Arc<dyn TimeSource>) {
+        if let Some(ref mut clock) = self.clock {
+            clock.set_time_source(source);
+        }
+    }
+
+    pub fn add_listener(&mut self, listener: Arc<dyn GameListener>) {
+        self.listeners.add(listener);
+    }
+
+    pub fn get_phase(&self, player: Player) -> Phase {
+        if self.is_in_placement_phase() {
+            Phase::Placing
+        } else if self.is_flying(player) {
+            Phase::Flying
+        } else {
+            Phase::Moving
+        }
+    }
+
+    pub fn get_draw_offer(&self) -> Option<Player> {
+        self.draw_offer
+    }
+
+    // every mill standing on the board, with its owner and points in the order the board lists them
+    pub fn mills(&self) -> Vec<(Player, [Location; 3])> {
+        self.board.get_mills().into_iter().filter_map(|mill| {
+            let owner = self.get_owner(mill[0])?;
+            if mill.iter().all(|&location| self.does_piece_exist(location, owner)) {
+                Some((owner, mill))
+            } else {
+                None
+            }
+        }).collect()
+    }
+
+    // false for empty points and men off the board
+    pub fn is_in_mill(&self, location: Location) -> bool {
+        match self.get_owner(location) {
+            Some(owner) => self.board.contains(location) && self.is_three_in_a_row(owner, location),
+            None => false
+        }
+    }
+
+    // the player's men the opponent could take on closing a mill. Men in mills are only
+    // included when the rules allow it, or when the player has no others.
+    pub fn removable_pieces(&self, player: Player) -> Vec<Location> {
+        let opponent = Self::switch_player(player);
+        self.pieces.iter()
+            .filter(|piece| piece.get_player() == player && self.board.contains(piece.get_location()))
+            .map(|piece| piece.get_location())
+            .filter(|&location| self.is_valid_removal(opponent, Some(location)))
+            .collect()
     }
 
     pub fn get_status(&self) -> GameStatus {
-        if self.get_player_captured_count(Player::One) > 6 {
-            return GameStatus::Win(Player::Two);
+        if let Some(result) = self.result {
+            return result;
         }
 
-        if self.get_player_captured_count(Player::Two) > 6 {
-            return GameStatus::Win(Player::One);
+        if self.get_player_remaining_count(Player::One) < 3 {
+            return GameStatus::Win(Player::Two, EndReason::MillCapture);
+        }
+
+        if self.get_player_remaining_count(Player::Two) < 3 {
+            return GameStatus::Win(Player::One, EndReason::MillCapture);
+        }
+
+        if self.rules.threefold_repetition && self.is_threefold_repetition() {
+            return GameStatus::Draw(EndReason::Repetition);
+        }
+
+        if !self.has_legal_move(self.next_player) {
+            return match self.rules.blocked {
+                BlockedOutcome::Loss => {
+                    GameStatus::Win(Self::switch_player(self.next_player), EndReason::Blockade)
+                },
+                BlockedOutcome::Draw => GameStatus::Draw(EndReason::Blockade)
+            };
+        }
+
+        // the clock keeps running after a repetition or blockade, which were settled by the
+        // move that made them, so running out of time afterwards doesn't change the result
+        if let Some(ref clock) = self.clock {
+            for &player in [Player::One, Player::Two].iter() {
+                if clock.get_time_left(player, player == self.next_player) == Duration::default() {
+                    return GameStatus::Win(Self::switch_player(player), EndReason::Timeout);
+                }
+            }
         }
 
         GameStatus::Playing
     }
 
-    pub fn submit(&mut self, game_move: GameMove) -> bool {
+    pub fn apply(&mut self, action: GameAction) -> bool {
+        if let GameAction::Move(game_move) = action {
+            return self.submit(game_move);
+        }
 
         if self.get_status() != GameStatus::Playing {
             return false;
         }
 
+        let player = action.get_player();
+        let offered_by_opponent = self.draw_offer == Some(Self::switch_player(player));
+
+        match action {
+            GameAction::Resign(_) => {
+                self.result = Some(GameStatus::Win(Self::switch_player(player), EndReason::Resignation));
+            },
+            GameAction::OfferDraw(_) | GameAction::AcceptDraw(_) if offered_by_opponent => {
+                self.result = Some(GameStatus::Draw(EndReason::Agreement));
+            },
+            GameAction::OfferDraw(_) => self.draw_offer = Some(player),
+            GameAction::DeclineDraw(_) if offered_by_opponent => self.draw_offer = None,
+            _ => return false
+        }
+
+        if let Some(result) = self.result {
+            self.listeners.notify_all(&[GameEvent::GameOver(result)]);
+        }
+
+        true
+    }
+
+    pub fn submit(&mut self, game_move: GameMove) -> bool {
+        self.play(game_move).is_ok()
+    }
+
+    // submits the move, and says what it did to the game
+    pub fn play(&mut self, game_move: GameMove) -> Result<Vec<GameEvent>, MoveError> {
+        self.validate(&game_move)?;
+
+        let phases = [self.get_phase(Player::One), self.get_phase(Player::Two)];
         let old_location = game_move.get_from();
         let new_location = game_move.get_to();
         let player = game_move.get_player();
-        let remove = game_move.get_remove();
+        let removals = game_move.get_removals();
 
-        if !self.is_valid_move(player, old_location, new_location) {
-            return false;
+        let mut events = vec!(match old_location {
+            Location::Hand => GameEvent::PiecePlaced(player, new_location),
+            _ => GameEvent::PieceMoved(player, old_location, new_location)
+        });
+
+        self.pieces = self.get_updated_pieces(player, old_location, new_location);
+
+        for mill in self.board.get_mills() {
+            if mill.contains(&new_location) && mill.iter().all(|&point| self.does_piece_exist(point, player)) {
+                events.push(GameEvent::MillFormed(player, mill));
+            }
         }
 
-        if !self.is_valid_removal(player, remove) {
-            return false;
+        // only men actually taken are reported, though validate lets nothing else through
+        let opponent = Self::switch_player(player);
+        for &remove in removals.iter() {
+            if self.board.contains(remove) && self.does_piece_exist(remove, opponent) {
+                self.pieces = self.get_updated_with_removed(player, Some(remove));
+                events.push(GameEvent::PieceCaptured(opponent, remove));
+            }
         }
 
-        self.pieces = self.get_updated_pieces(player, old_location, new_location);
+        if let Some(ref mut clock) = self.clock {
+            clock.record(player);
+        }
 
-        if self.is_three_in_a_row(player, new_location) {
-            self.pieces = self.get_updated_with_removed(player, remove);
+        // moving on instead of answering turns a draw offer down
+        if self.draw_offer == Some(Self::switch_player(player)) {
+            self.draw_offer = None;
         }
 
+        self.history.push(game_move);
         self.turn += 1;
         self.next_player = Self::switch_player(self.next_player);
 
-        true
+        match self.get_status() {
+            GameStatus::Playing => {
+                for (&player, &phase) in [Player::One, Player::Two].iter().zip(phases.iter()) {
+                    if self.get_phase(player) != phase {
+                        events.push(GameEvent::PhaseChanged(player, self.get_phase(player)));
+                    }
+                }
+                events.push(GameEvent::TurnChanged(self.next_player));
+            },
+            status => events.push(GameEvent::GameOver(status))
+        }
+
+        self.listeners.notify_all(&events);
+        Ok(events)
+    }
+
+    // why submit would turn the move down, if it would
+    pub fn validate(&self, game_move: &GameMove) -> Result<(), MoveError> {
+        if self.get_status() != GameStatus::Playing {
+            return Err(MoveError::GameOver);
+        }
+
+        let player = game_move.get_player();
+        let removals = game_move.get_removals();
+
+        self.check_move(player, game_move.get_from(), game_move.get_to())?;
+
+        if !self.are_valid_removals(player, &removals) {
+            return Err(MoveError::InvalidRemoval);
+        }
+
+        let captures = self.get_capture_count(game_move);
+        if removals.len() > captures {
+            return Err(MoveError::TooManyRemovals);
+        }
+
+        // every man the opponent has on the board can be taken in some order, so a mill
+        // takes as many as it earns until there are none left
+        if removals.len() < cmp::min(captures, self.get_removable_count(player)) {
+            return Err(MoveError::MissingRemoval);
+        }
+
+        Ok(())
+    }
+
+    // everything the player to move could submit, taking as many men as each move allows
+    pub fn get_legal_moves(&self) -> Vec<GameMove> {
+        let player = self.next_player;
+        let opponent = Self::switch_player(player);
+        let points = self.board.get_points();
+        let targets: Vec<Location> = points.iter().cloned().filter(|&location| {
+            self.does_piece_exist(location, opponent)
+        }).collect();
+        let mut froms = vec!(Location::Hand);
+        froms.extend(points.iter().filter(|&&location| self.does_piece_exist(location, player)));
+
+        let mut moves = vec!();
+        if self.get_status() != GameStatus::Playing {
+            return moves;
+        }
+
+        for &from in froms.iter() {
+            for &to in points.iter() {
+                if self.check_move(player, from, to).is_err() {
+                    continue;
+                }
+
+                // when no set of the full size can be taken, fewer men are, so the move stays
+                let game_move = GameMove::new(player, from, to, None);
+                let most = cmp::min(self.get_capture_count(&game_move), targets.len());
+                for captures in (0..most + 1).rev() {
+                    let found: Vec<GameMove> = Self::get_combinations(&targets, captures).iter()
+                        .filter_map(|set| self.get_removal_order(player, set))
+                        .map(|removals| GameMove::with_removals(player, from, to, removals))
+                        .collect();
+                    if !found.is_empty() {
+                        moves.extend(found);
+                        break;
+                    }
+                }
+            }
+        }
+
+        moves
+    }
+
+    fn get_combinations(locations: &[Location], count: usize) -> Vec<Vec<Location>> {
+        if count == 0 {
+            return vec!(vec!());
+        }
+
+        let mut combinations = vec!();
+        for (i, &location) in locations.iter().enumerate() {
+            for mut rest in Self::get_combinations(&locations[i + 1..], count - 1) {
+                rest.insert(0, location);
+                combinations.push(rest);
+            }
+        }
+        combinations
+    }
+
+    // an order the men can be taken in, since each is judged once the ones before it are gone
+    fn get_removal_order(&self, player: Player, removals: &[Location]) -> Option<Vec<Location>> {
+        if removals.is_empty() {
+            return Some(vec!());
+        }
+
+        removals.iter().enumerate().filter_map(|(i, &first)| {
+            if !self.is_valid_removal(player, Some(first)) {
+                return None;
+            }
+
+            let mut position = self.clone();
+            position.pieces = position.get_updated_with_removed(player, Some(first));
+            let rest: Vec<Location> = removals.iter().enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, &location)| location)
+                .collect();
+            position.get_removal_order(player, &rest).map(|mut order| {
+                order.insert(0, first);
+                order
+            })
+        }).next()
     }
 
     fn is_valid_move(
@@ -122,22 +553,147 @@ impl Game {
         old_location: Location, 
         new_location: Location
     ) -> bool {
+        self.check_move(player, old_location, new_location).is_ok()
+    }
+
+    fn check_move(
+        &self, 
+        player: Player, 
+        old_location: Location, 
+        new_location: Location
+    ) -> Result<(), MoveError> {
         if player != self.next_player {
-            return false;
+            return Err(MoveError::NotYourTurn);
+        }
+
+        if !self.does_piece_exist(old_location, player) {
+            return Err(MoveError::NoManToMove);
         }
 
         if old_location != Location::Hand && self.is_in_placement_phase() {
-            return false;
+            return Err(MoveError::StillPlacing);
+        }
+
+        if !self.board.contains(new_location) {
+            return Err(MoveError::NotOnBoard);
         }
 
         if self.is_location_occupied(new_location) {
-            return false;
+            return Err(MoveError::Occupied);
+        }
+
+        if !self.is_in_placement_phase() 
+            && !self.is_flying(player)
+            && !self.is_next_door(old_location, new_location) {
+            return Err(MoveError::NotAdjacent);
         }
 
-        if !self.is_in_placement_phase() && !self.is_next_door(old_location, new_location) {
+        Ok(())
+    }
+
+    // how many men the move may remove under the rules
+    pub fn get_capture_count(&self, game_move: &GameMove) -> usize {
+        let mills = self.count_mills_closed(
+            game_move.get_player(),
+            game_move.get_from(),
+            game_move.get_to()
+        );
+
+        if mills == 0 || (!self.rules.reform_mill_immediately && self.is_reforming_mill(game_move)) {
+            return 0;
+        }
+
+        if self.rules.capture_per_mill {
+            mills
+        } else {
+            1
+        }
+    }
+
+    fn is_reforming_mill(&self, game_move: &GameMove) -> bool {
+        let previous_move = self.history.iter().rev().find(|&previous_move| {
+            previous_move.get_player() == game_move.get_player()
+        });
+
+        match previous_move {
+            Some(previous_move) => {
+                previous_move.get_from() == game_move.get_to()
+                    && previous_move.get_to() == game_move.get_from()
+            },
+            None => false
+        }
+    }
+
+    fn is_flying(&self, player: Player) -> bool {
+        self.rules.flying && self.get_player_remaining_count(player) == 3
+    }
+
+    // positions can only repeat while men slide without capturing, so the earlier ones
+    // are found by taking those moves back from the current position
+    fn is_threefold_repetition(&self) -> bool {
+        let points = self.board.get_points();
+        let index = |location| points.iter().position(|&point| point == location);
+        let mut position: Vec<Option<Player>> = points.iter().map(|&location| {
+            self.pieces.iter()
+                .find(|&piece| piece.get_location() == location)
+                .map(|piece| piece.get_player())
+        }).collect();
+        let current = position.clone();
+        let mut repeats = 1;
+
+        for (moves_back, game_move) in self.history.iter().rev().enumerate() {
+            let (from, to) = match (index(game_move.get_from()), index(game_move.get_to())) {
+                (Some(from), Some(to)) if game_move.get_removals().is_empty() => (from, to),
+                _ => break
+            };
+
+            position[from] = position[to].take();
+            if moves_back % 2 == 1 && position == current {
+                repeats += 1;
+            }
+        }
+
+        repeats >= 3
+    }
+
+    fn has_legal_move(&self, player: Player) -> bool {
+        let empty_points: Vec<Location> = self.board.get_points().into_iter().filter(|&location| {
+            !self.is_location_occupied(location)
+        }).collect();
+
+        if self.is_in_placement_phase() || self.is_flying(player) {
+            return !empty_points.is_empty();
+        }
+
+        self.pieces.iter().any(|&piece| {
+            piece.get_player() == player
+                && piece.get_location() != Location::Hand
+                && piece.get_location() != Location::Captured
+                && empty_points.iter().any(|&location| {
+                    self.is_next_door(piece.get_location(), location)
+                })
+        })
+    }
+
+    fn are_valid_removals(&self, player: Player, removals: &[Location]) -> bool {
+        let distinct = removals.iter().enumerate().all(|(i, location)| {
+            !removals[..i].contains(location)
+        });
+
+        if !distinct {
             return false;
         }
 
+        // each man is judged once the ones before it are gone, since taking the last man
+        // outside a mill leaves the mills open to the next capture
+        let mut position = self.clone();
+        for &location in removals.iter() {
+            if !position.is_valid_removal(player, Some(location)) {
+                return false;
+            }
+            position.pieces = position.get_updated_with_removed(player, Some(location));
+        }
+
         true
     }
 
@@ -146,8 +702,11 @@ impl Game {
             None => true,
             Some(location) => {
                 let other_player = Self::switch_player(player);
-                !self.is_three_in_a_row(other_player, location) 
-                    || !self.does_player_have_non_mill_pieces(other_player)
+                self.board.contains(location)
+                    && self.does_piece_exist(location, other_player)
+                    && (self.rules.capture_from_mills
+                        || !self.is_three_in_a_row(other_player, location)
+                        || !self.does_player_have_non_mill_pieces(other_player))
             }
         }
     }
@@ -165,14 +724,7 @@ impl Game {
     }
 
     fn is_next_door(&self, new_location: Location, old_location: Location) -> bool {
-        let rows = Location::get_rows(old_location).unwrap();
-        for (a, b) in rows {
-            if a == new_location || b == new_location {
-                return true;
-            }
-        }
-
-        false
+        self.board.is_adjacent(old_location, new_location)
     }
 
     fn does_player_have_non_mill_pieces(&self, player: Player) -> bool {
@@ -196,7 +748,7 @@ impl Game {
                 piece_moved = true;
                 Piece::new(player, new_location)
             } else {
-                piece.clone()
+                piece
             }
         }).collect()
     }
@@ -209,7 +761,7 @@ impl Game {
                         && piece.get_player() != player {
                         Piece::new(piece.get_player(), Location::Captured)
                     } else {
-                        piece.clone()
+                        piece
                     }
                }).collect()
             },
@@ -219,8 +771,7 @@ impl Game {
 
 
     fn is_three_in_a_row(&self, player: Player, new_location: Location) -> bool {
-        let new_location_output = format!("{:?}", new_location);
-        let rows = Location::get_rows(new_location).expect(&new_location_output);
+        let rows = self.get_rows(new_location);
 
         for (a, b) in rows {
             if self.does_piece_exist(a, player) && self.does_piece_exist(b, player) {
@@ -231,15 +782,46 @@ impl Game {
         false
     }
 
+    // counted before the move, so the point being left can't be part of the mill
+    fn count_mills_closed(
+        &self,
+        player: Player,
+        old_location: Location,
+        new_location: Location
+    ) -> usize {
+        self.get_rows(new_location).iter().filter(|&&(a, b)| {
+            a != old_location && b != old_location
+                && self.does_piece_exist(a, player) && self.does_piece_exist(b, player)
+        }).count()
+    }
+
+    fn get_rows(&self, location: Location) -> Vec<(Location, Location)> {
+        self.board.get_rows(location)
+    }
+
     fn does_piece_exist(&self, location: Location, player: Player) -> bool {
         self.pieces.iter().any(|&piece| {
             piece.get_location() == location && piece.get_player() == player
         })
     }
 
-    fn get_player_captured_count(&self, player: Player) -> u8 {
+    fn get_owner(&self, location: Location) -> Option<Player> {
+        self.pieces.iter()
+            .find(|&piece| piece.get_location() == location)
+            .map(|piece| piece.get_player())
+    }
+
+    // the opponent's men on the board, which is how many the player could take
+    fn get_removable_count(&self, player: Player) -> usize {
+        let opponent = Self::switch_player(player);
+        self.pieces.iter().filter(|piece| {
+            piece.get_player() == opponent && self.board.contains(piece.get_location())
+        }).count()
+    }
+
+    fn get_player_remaining_count(&self, player: Player) -> u8 {
         self.pieces.iter().filter(|&piece| {
-            piece.get_location() == Location::Captured
+            piece.get_location() != Location::Captured
                 && piece.get_player() == player
         }).count() as u8
     }
@@ -253,36 +835,150 @@ impl Game {
         }).collect()
     }
 
-    fn get_new_pieces() -> Vec<Piece> {
-        vec!(
-            piece!(One, Hand),
-            piece!(One, Hand),
-            piece!(One, Hand),
-            piece!(One, Hand),
-            piece!(One, Hand),
-            piece!(One, Hand),
-            piece!(One, Hand),
-            piece!(One, Hand),
-            piece!(One, Hand),
-            piece!(Two, Hand),
-            piece!(Two, Hand),
-            piece!(Two, Hand),
-            piece!(Two, Hand),
-            piece!(Two, Hand),
-            piece!(Two, Hand),
-            piece!(Two, Hand),
-            piece!(Two, Hand),
-            piece!(Two, Hand)
-        )
-    }
-
-    fn unwrap_pieces(v: Value) -> Vec<Piece> {
-        v.as_array().unwrap().iter().map(|ref x| {
-            Piece::new(
-                Self::unwrap_player(x["player"].as_u64().unwrap()), 
-                Location::from_str(x["location"].as_str().unwrap())
-            )
-        }).collect()
+    fn wrap_history(&self) -> Vec<Value> {
+        self.history.iter().map(|game_move| game_move.to_value()).collect()
+    }
+
+    fn wrap_rules(&self) -> Value {
+        json!({
+            "variant": self.rules.variant.to_str(),
+            "capture_from_mills": self.rules.capture_from_mills,
+            "flying": self.rules.flying,
+            "reform_mill_immediately": self.rules.reform_mill_immediately,
+            "capture_per_mill": self.rules.capture_per_mill,
+            "blocked": self.rules.blocked.to_str(),
+            "threefold_repetition": self.rules.threefold_repetition
+        })
+    }
+
+    fn wrap_clock(clock: &Clock) -> Value {
+        let millis = |duration: Duration| duration.as_millis() as u64;
+        let mut json = match clock.get_control() {
+            TimeControl::SuddenDeath(base) => json!({
+                "control": "SuddenDeath",
+                "base": millis(base)
+            }),
+            TimeControl::Fischer(base, increment) => json!({
+                "control": "Fischer",
+                "base": millis(base),
+                "increment": millis(increment)
+            }),
+            TimeControl::Bronstein(base, delay) => json!({
+                "control": "Bronstein",
+                "base": millis(base),
+                "delay": millis(delay)
+            })
+        };
+
+        json["remaining"] = json!([
+            millis(clock.get_time_left(Player::One, false)),
+            millis(clock.get_time_left(Player::Two, false))
+        ]);
+        json["move_times"] = json!(clock.get_move_times().into_iter().map(millis).collect::<Vec<u64>>());
+        json["turn_elapsed"] = json!(millis(clock.get_turn_elapsed()));
+        json
+    }
+
+    fn get_new_pieces(variant: Variant) -> Vec<Piece> {
+        let count = variant.get_piece_count();
+        let mut pieces = vec!(piece!(One, Hand); count);
+        pieces.extend(vec!(piece!(Two, Hand); count));
+        pieces
+    }
+
+    fn unwrap_history(v: Value) -> Result<Vec<GameMove>, Error> {
+        match v.as_array() {
+            Some(moves) => moves.iter().map(GameMove::from_value).collect(),
+            None => Ok(vec!())
+        }
+    }
+
+    // anything left out falls back to the defaults for the variant
+    fn unwrap_rules(v: Value) -> Result<RuleSet, Error> {
+        let variant = match v["variant"].as_str() {
+            Some(code) => code.parse().map_err(invalid)?,
+            None => Variant::NineMensMorris
+        };
+        let defaults = RuleSet::for_variant(variant);
+
+        Ok(RuleSet {
+            variant,
+            capture_from_mills: v["capture_from_mills"].as_bool()
+                .unwrap_or(defaults.capture_from_mills),
+            flying: v["flying"].as_bool().unwrap_or(defaults.flying),
+            reform_mill_immediately: v["reform_mill_immediately"].as_bool()
+                .unwrap_or(defaults.reform_mill_immediately),
+            capture_per_mill: v["capture_per_mill"].as_bool().unwrap_or(defaults.capture_per_mill),
+            blocked: match v["blocked"].as_str() {
+                Some(code) => code.parse().map_err(invalid)?,
+                None => defaults.blocked
+            },
+            threefold_repetition: v["threefold_repetition"].as_bool()
+                .unwrap_or(defaults.threefold_repetition)
+        })
+    }
+
+    fn wrap_result(result: GameStatus) -> Value {
+        match result {
+            GameStatus::Win(player, reason) => json!({
+                "status": "Win",
+                "player": Self::wrap_player(player),
+                "reason": reason.to_str()
+            }),
+            GameStatus::Draw(reason) => json!({
+                "status": "Draw",
+                "reason": reason.to_str()
+            }),
+            GameStatus::Playing => json!({ "status": "Playing" })
+        }
+    }
+
+    fn unwrap_result(v: &Value) -> Result<Option<GameStatus>, Error> {
+        let reason = || match v["reason"].as_str() {
+            Some(code) => code.parse::<EndReason>().map_err(invalid),
+            None => Err(invalid(format!("expected an end reason, found {}", v["reason"])))
+        };
+        match v["status"].as_str() {
+            Some("Win")     => Ok(Some(GameStatus::Win(Self::unwrap_player_value(&v["player"])?, reason()?))),
+            Some("Draw")    => Ok(Some(GameStatus::Draw(reason()?))),
+            _               => Ok(None)
+        }
+    }
+
+    fn unwrap_clock(v: &Value) -> Result<Option<Clock>, Error> {
+        let control = match v["control"].as_str() {
+            Some(control) => control,
+            None => return Ok(None)
+        };
+        let millis = |v: &Value| match v.as_u64() {
+            Some(millis) => Ok(Duration::from_millis(millis)),
+            None => Err(invalid(format!("expected milliseconds, found {}", v)))
+        };
+        let base = millis(&v["base"])?;
+        let control = match control {
+            "Fischer"   => TimeControl::Fischer(base, millis(&v["increment"])?),
+            "Bronstein" => TimeControl::Bronstein(base, millis(&v["delay"])?),
+            _           => TimeControl::SuddenDeath(base)
+        };
+        let remaining = [millis(&v["remaining"][0])?, millis(&v["remaining"][1])?];
+        let move_times = match v["move_times"].as_array() {
+            Some(times) => times.iter().map(millis).collect::<Result<Vec<Duration>, Error>>()?,
+            None => return Err(invalid(format!("expected move times, found {}", v["move_times"])))
+        };
+
+        // saves from before the running turn was kept start it afresh
+        let turn_elapsed = v["turn_elapsed"].as_u64().map_or(Duration::default(), Duration::from_millis);
+
+        Ok(Some(Clock::restore(control, remaining, move_times, turn_elapsed)))
+    }
+
+    fn unwrap_pieces(v: &Value) -> Result<Vec<Piece>, Error> {
+        match v.as_array() {
+            Some(pieces) => pieces.iter().map(|x| {
+                Ok(Piece::new(Self::unwrap_player_value(&x["player"])?, Self::unwrap_location(&x["location"])?))
+            }).collect(),
+            None => Err(invalid(format!("expected pieces, found {}", v)))
+        }
     }
 
     fn unwrap_player(player: u64) -> Player {
@@ -293,6 +989,23 @@ impl Game {
         }
     }
 
+    // unlike unwrap_player, anything but 1 or 2 is an error
+    fn unwrap_player_value(v: &Value) -> Result<Player, Error> {
+        match v.as_u64() {
+            Some(player @ 1) | Some(player @ 2) => Ok(Self::unwrap_player(player)),
+            _ => Err(invalid(format!("expected player 1 or 2, found {}", v)))
+        }
+    }
+
+    fn unwrap_location(v: &Value) -> Result<Location, Error> {
+        match v.as_str() {
+            Some("Hand") => Ok(Location::Hand),
+            Some("Captured") => Ok(Location::Captured),
+            Some(code) => Location::parse(code).ok_or_else(|| invalid(format!("unknown location \"{}\"", code))),
+            None => Err(invalid(format!("expected a location, found {}", v)))
+        }
+    }
+
     fn wrap_player(player: Player) -> u64 {
         match player {
             Player::Two => 2,
@@ -309,3 +1022,8 @@ impl Game {
 }
 
 
+
+// a save that parses as JSON but doesn't describe a game, reported like a parse error
+fn invalid<T: std::fmt::Display>(message: T) -> Error {
+    serde::de::Error::custom(message)
+}
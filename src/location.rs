@@ -1,3 +1,5 @@
+use board::STANDARD_BOARD;
+
 #[derive(PartialEq, Debug, Clone, Copy)]
 pub enum Location {
     Hand,
@@ -29,6 +31,7 @@ pub enum Location {
 }
 
 impl Location {
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(code: &str) -> Location {
         use Location::*;
         match code {
@@ -62,6 +65,13 @@ impl Location {
         }
     }
 
+    // a point on the board from its coordinate, in either case
+    pub fn parse(code: &str) -> Option<Location> {
+        Self::get_points().into_iter().find(|location| {
+            location.to_str().eq_ignore_ascii_case(code)
+        })
+    }
+
     pub fn to_str(&self) -> &'static str {
         use Location::*;
         match *self {
@@ -95,6 +105,36 @@ impl Location {
 
     }
 
+    pub fn get_points() -> Vec<Location> {
+        use Location::*;
+        vec!(
+            A7, A4, A1,
+            B6, B4, B2,
+            C5, C4, C3,
+            D7, D6, D5, D3, D2, D1,
+            E5, E4, E3,
+            F6, F4, F2,
+            G7, G4, G1
+        )
+    }
+
+    // file and rank on the diagram, counted from a1 as (0, 0)
+    pub fn get_coordinates(&self) -> Option<(u8, u8)> {
+        match *self {
+            Location::Hand | Location::Captured => None,
+            _ => {
+                let code = self.to_str().as_bytes();
+                Some((code[0] - b'A', code[1] - b'1'))
+            }
+        }
+    }
+
+    // the points a man here could slide to on the standard board
+    pub fn neighbors(&self) -> Vec<Location> {
+        STANDARD_BOARD.get_neighbors(*self)
+    }
+
+    #[allow(clippy::result_unit_err)]
     pub fn get_rows(location: Location) -> Result<Vec<(Location, Location)>, ()> {
         use Location::*;
         match location {
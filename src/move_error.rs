@@ -0,0 +1,36 @@
+use std::fmt;
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum MoveError {
+    GameOver,
+    NotYourTurn,
+    NoManToMove,
+    StillPlacing,
+    NotOnBoard,
+    Occupied,
+    NotAdjacent,
+    InvalidRemoval,
+    TooManyRemovals,
+    MissingRemoval
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match *self {
+            MoveError::GameOver         => "the game is over",
+            MoveError::NotYourTurn      => "it's the other player's turn",
+            MoveError::NoManToMove      => "you don't have a man there to move",
+            MoveError::StillPlacing     => "men can't be moved until every man has been placed",
+            MoveError::NotOnBoard       => "that point isn't on the board",
+            MoveError::Occupied         => "that point is already taken",
+            MoveError::NotAdjacent      => "men can only move to an adjacent point",
+            MoveError::InvalidRemoval   => {
+                "only the opponent's men can be removed, and not from a mill while others are left"
+            },
+            MoveError::TooManyRemovals  => "that move doesn't close enough mills for those removals",
+            MoveError::MissingRemoval   => "that move closes a mill, so it has to say which men to remove"
+        };
+
+        write!(f, "{}", message)
+    }
+}
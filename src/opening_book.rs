@@ -0,0 +1,209 @@
+use std::cmp;
+use std::collections::BTreeMap;
+use Game;
+use GameMove;
+use Location;
+use Player;
+use Symmetry;
+use Variant;
+
+const MAGIC: &[u8; 4] = b"NMMB";
+// version 1 keys didn't say which variant the position was from
+const VERSION: u8 = 2;
+
+// moves seen from each opening position, with how often they were played. Positions are
+// stored in the orientation with the smallest key, so the 16 symmetric copies of a
+// position share one entry.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct OpeningBook {
+    // position key to (action index, weight), in the canonical orientation
+    entries: BTreeMap<u64, Vec<(u16, u32)>>
+}
+
+impl OpeningBook {
+    pub fn new() -> OpeningBook {
+        OpeningBook::default()
+    }
+
+    // replays the first `depth` moves of each game's history from the start
+    pub fn from_games(games: &[Game], depth: usize) -> OpeningBook {
+        let mut book = OpeningBook::new();
+        for game in games.iter() {
+            let mut replay = Game::with_board(game.get_rules(), game.get_board());
+            for game_move in game.get_history().into_iter().take(depth) {
+                if replay.validate(&game_move).is_err() {
+                    break;
+                }
+                book.add(&replay, &game_move, 1);
+                replay.submit(game_move);
+            }
+        }
+        book
+    }
+
+    // moves with two removals have no action index, and are left out
+    pub fn add(&mut self, game: &Game, game_move: &GameMove, weight: u32) {
+        let (key, symmetry) = get_canonical_key(game);
+        let index = match symmetry.apply_to_move(game_move).get_action_index() {
+            Some(index) => index as u16,
+            None => return
+        };
+
+        let moves = self.entries.entry(key).or_default();
+        match moves.iter_mut().find(|&&mut (action, _)| action == index) {
+            Some(&mut (_, ref mut total)) => *total = total.saturating_add(weight),
+            None => moves.push((index, weight))
+        }
+    }
+
+    pub fn get_moves(&self, game: &Game) -> Vec<(GameMove, u32)> {
+        let (key, symmetry) = get_canonical_key(game);
+        let inverse = symmetry.inverse();
+        let player = game.get_next_player();
+
+        self.entries.get(&key).map_or(vec!(), |moves| {
+            moves.iter().filter_map(|&(index, weight)| {
+                let game_move = GameMove::from_action_index(player, index as usize)?;
+                Some((inverse.apply_to_move(&game_move), weight))
+            }).collect()
+        })
+    }
+
+    // picks a book move with chance in proportion to its weight; `roll` is a random number
+    // from 0 to 1
+    pub fn choose(&self, game: &Game, roll: f64) -> Option<GameMove> {
+        let moves = self.get_moves(game);
+        let total: u64 = moves.iter().map(|&(_, weight)| weight as u64).sum();
+        // a roll of 1 picks the last move rather than falling off the end
+        let mut target = cmp::min((roll.max(0.0) * total as f64) as u64, total.saturating_sub(1));
+
+        for (game_move, weight) in moves {
+            if target < weight as u64 {
+                return Some(game_move);
+            }
+            target -= weight as u64;
+        }
+        None
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // "NMMB", a version byte and the entry count, then for each position its key, move count,
+    // and each move's action index and weight; all little-endian
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&(self.entries.len() as u32).to_le_bytes());
+
+        for (&key, moves) in self.entries.iter() {
+            bytes.extend_from_slice(&key.to_le_bytes());
+            bytes.extend_from_slice(&(moves.len() as u16).to_le_bytes());
+            for &(index, weight) in moves.iter() {
+                bytes.extend_from_slice(&index.to_le_bytes());
+                bytes.extend_from_slice(&weight.to_le_bytes());
+            }
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<OpeningBook, String> {
+        if bytes.len() < 9 || &bytes[..4] != MAGIC {
+            return Err(String::from("not an opening book"));
+        }
+        if bytes[4] != VERSION {
+            return Err(format!("unknown opening book version {}", bytes[4]));
+        }
+
+        let mut reader = Reader { bytes, position: 5 };
+        let mut entries = BTreeMap::new();
+        for _ in 0..reader.read_u32()? {
+            let key = reader.read_u64()?;
+            let count = reader.read_u16()?;
+            let mut moves = vec!();
+            for _ in 0..count {
+                moves.push((reader.read_u16()?, reader.read_u32()?));
+            }
+            entries.insert(key, moves);
+        }
+
+        Ok(OpeningBook { entries })
+    }
+}
+
+// the smallest key among the position's symmetric copies, and the symmetry that gives it
+fn get_canonical_key(game: &Game) -> (u64, Symmetry) {
+    Symmetry::all().into_iter()
+        .map(|symmetry| (get_position_key(game, symmetry), symmetry))
+        .min_by_key(|&(key, _)| key)
+        .unwrap()
+}
+
+// one bit for the variant, two for each point's owner, four for each side's men in hand, and one
+// for the side to move
+fn get_position_key(game: &Game, symmetry: Symmetry) -> u64 {
+    let mut cells = [0u64; 24];
+    let mut in_hand = [0u64; 2];
+    let points = Location::get_points();
+
+    for piece in game.get_pieces().iter() {
+        let owner = if piece.get_player() == Player::One { 1 } else { 2 };
+        match piece.get_location() {
+            Location::Hand => in_hand[owner as usize - 1] += 1,
+            Location::Captured => {},
+            location => {
+                let point = symmetry.apply(location);
+                if let Some(i) = points.iter().position(|&other| other == point) {
+                    cells[i] = owner;
+                }
+            }
+        }
+    }
+
+    let variant = match game.get_variant() {
+        Variant::NineMensMorris => 0,
+        Variant::Morabaraba => 1
+    };
+    let mut key = cells.iter().fold(variant, |key, &cell| key << 2 | cell);
+    key = key << 4 | in_hand[0];
+    key = key << 4 | in_hand[1];
+    key << 1 | if game.get_next_player() == Player::Two { 1 } else { 0 }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, count: usize) -> Result<&'a [u8], String> {
+        let end = self.position + count;
+        if end > self.bytes.len() {
+            return Err(String::from("the opening book is cut short"));
+        }
+        let taken = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(taken)
+    }
+
+    fn read_u16(&mut self) -> Result<u16, String> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, String> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, String> {
+        let mut buffer = [0; 8];
+        buffer.copy_from_slice(self.take(8)?);
+        Ok(u64::from_le_bytes(buffer))
+    }
+}
@@ -0,0 +1,79 @@
+use std::str::FromStr;
+use Variant;
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum BlockedOutcome {
+    Loss,
+    Draw
+}
+
+impl FromStr for BlockedOutcome {
+    type Err = String;
+
+    fn from_str(code: &str) -> Result<BlockedOutcome, String> {
+        match code {
+            "Loss"  => Ok(BlockedOutcome::Loss),
+            "Draw"  => Ok(BlockedOutcome::Draw),
+            _       => Err(format!("unknown blocked outcome \"{}\"", code))
+        }
+    }
+}
+
+impl BlockedOutcome {
+    pub fn to_str(&self) -> &'static str {
+        match *self {
+            BlockedOutcome::Loss    => "Loss",
+            BlockedOutcome::Draw    => "Draw"
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct RuleSet {
+    pub variant: Variant,
+    // pieces in a mill can be taken even when the opponent has others to take
+    pub capture_from_mills: bool,
+    pub flying: bool,
+    // a mill broken and remade with the very next move still captures
+    pub reform_mill_immediately: bool,
+    // a move closing two mills at once captures two pieces
+    pub capture_per_mill: bool,
+    pub blocked: BlockedOutcome,
+    // the same position coming round three times draws the game
+    pub threefold_repetition: bool
+}
+
+impl Default for RuleSet {
+    fn default() -> RuleSet {
+        RuleSet::standard()
+    }
+}
+
+impl RuleSet {
+    pub fn standard() -> RuleSet {
+        RuleSet {
+            variant: Variant::NineMensMorris,
+            capture_from_mills: false,
+            flying: true,
+            reform_mill_immediately: true,
+            capture_per_mill: false,
+            blocked: BlockedOutcome::Loss,
+            threefold_repetition: true
+        }
+    }
+
+    pub fn morabaraba() -> RuleSet {
+        RuleSet {
+            variant: Variant::Morabaraba,
+            reform_mill_immediately: false,
+            ..RuleSet::standard()
+        }
+    }
+
+    pub fn for_variant(variant: Variant) -> RuleSet {
+        match variant {
+            Variant::NineMensMorris => RuleSet::standard(),
+            Variant::Morabaraba     => RuleSet::morabaraba()
+        }
+    }
+}
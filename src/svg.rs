@@ -0,0 +1,175 @@
+use {Game, GameMove, Location, Player};
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct SvgOptions {
+    // width and height of the drawing in pixels
+    pub size: u32,
+    pub background: String,
+    pub lines: String,
+    pub player_one: String,
+    pub player_two: String,
+    pub highlight: String,
+    pub labels: bool
+}
+
+impl Default for SvgOptions {
+    fn default() -> SvgOptions {
+        SvgOptions {
+            size: 400,
+            background: String::from("#f0d9a0"),
+            lines: String::from("#000000"),
+            player_one: String::from("#ffffff"),
+            player_two: String::from("#222222"),
+            highlight: String::from("#d62828"),
+            labels: true
+        }
+    }
+}
+
+pub fn render(game: &Game, options: &SvgOptions) -> String {
+    render_with_move(game, None, options)
+}
+
+// the move is drawn as an arrow, or a ring for a placement, with a cross on each removal
+pub fn render_with_move(game: &Game, game_move: Option<&GameMove>, options: &SvgOptions) -> String {
+    let unit = options.size as f64 / 8.0;
+    let mut svg = vec!(
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{0}" viewBox="0 0 {0} {0}">"#,
+            options.size
+        ),
+        format!(
+            r#"<rect width="{0}" height="{0}" fill="{1}"/>"#,
+            options.size, escape(&options.background)
+        )
+    );
+
+    let board = game.get_board();
+    for (a, b) in board.get_edges() {
+        let ((x1, y1), (x2, y2)) = match (get_position(a, unit), get_position(b, unit)) {
+            (Some(start), Some(end)) => (start, end),
+            _ => continue
+        };
+        svg.push(format!(
+            r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="{}"/>"#,
+            x1, y1, x2, y2, escape(&options.lines), unit / 20.0
+        ));
+    }
+
+    for location in board.get_points() {
+        let (x, y) = match get_position(location, unit) {
+            Some(position) => position,
+            None => continue
+        };
+        svg.push(format!(
+            r#"<circle class="point" cx="{}" cy="{}" r="{}" fill="{}"/>"#,
+            x, y, unit / 8.0, escape(&options.lines)
+        ));
+    }
+
+    for piece in game.get_pieces() {
+        let (x, y) = match get_position(piece.get_location(), unit) {
+            Some(position) if board.contains(piece.get_location()) => position,
+            _ => continue
+        };
+        let colour = match piece.get_player() {
+            Player::One => &options.player_one,
+            Player::Two => &options.player_two
+        };
+        svg.push(format!(
+            r#"<circle class="piece" cx="{}" cy="{}" r="{}" fill="{}" stroke="{}" stroke-width="{}"/>"#,
+            x, y, unit / 3.0, escape(colour), escape(&options.lines), unit / 20.0
+        ));
+    }
+
+    if let Some(game_move) = game_move {
+        svg.extend(render_move(game_move, unit, options));
+    }
+
+    if options.labels {
+        svg.extend(render_labels(unit, options));
+    }
+
+    svg.push(String::from("</svg>"));
+    svg.join("\n") + "\n"
+}
+
+fn render_move(game_move: &GameMove, unit: f64, options: &SvgOptions) -> Vec<String> {
+    let highlight = escape(&options.highlight);
+    let stroke_width = unit / 10.0;
+    let mut svg = vec!();
+
+    // a move that ends off the board, or where it started, has nothing to point at
+    let (to_x, to_y) = match get_position(game_move.get_to(), unit) {
+        Some(position) => position,
+        None => return svg
+    };
+    let from = get_position(game_move.get_from(), unit);
+
+    if from.is_none() {
+        svg.push(format!(
+            r#"<circle class="placement" cx="{}" cy="{}" r="{}" fill="none" stroke="{}" stroke-width="{}"/>"#,
+            to_x, to_y, unit / 2.4, highlight, stroke_width
+        ));
+    } else if let Some((from_x, from_y)) = from.filter(|&position| position != (to_x, to_y)) {
+        // stop short of the point so the arrowhead sits on the edge of the man
+        let (dx, dy) = (to_x - from_x, to_y - from_y);
+        let shorten = unit / 2.4 / (dx * dx + dy * dy).sqrt();
+        svg.push(format!(
+            concat!(
+                r#"<defs><marker id="arrowhead" markerWidth="4" markerHeight="4" refX="2" refY="2" orient="auto">"#,
+                r#"<path d="M0,0 L4,2 L0,4 z" fill="{}"/></marker></defs>"#
+            ),
+            highlight
+        ));
+        svg.push(format!(
+            r#"<line class="arrow" x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="{}" marker-end="url(#arrowhead)"/>"#,
+            from_x, from_y, to_x - dx * shorten, to_y - dy * shorten, highlight, stroke_width
+        ));
+    }
+
+    for location in game_move.get_removals() {
+        let (x, y) = match get_position(location, unit) {
+            Some(position) => position,
+            None => continue
+        };
+        let arm = unit / 3.0;
+        svg.push(format!(
+            r#"<path class="removal" d="M{},{} L{},{} M{},{} L{},{}" stroke="{}" stroke-width="{}"/>"#,
+            x - arm, y - arm, x + arm, y + arm, x - arm, y + arm, x + arm, y - arm,
+            highlight, stroke_width
+        ));
+    }
+
+    svg
+}
+
+fn render_labels(unit: f64, options: &SvgOptions) -> Vec<String> {
+    let font_size = unit / 3.0;
+    let colour = escape(&options.lines);
+    let files = "abcdefg".chars().enumerate().map(|(file, label)| {
+        format!(
+            r#"<text x="{}" y="{}" font-size="{}" font-family="sans-serif" text-anchor="middle" fill="{}">{}</text>"#,
+            (file as f64 + 1.0) * unit, 7.75 * unit, font_size, colour, label
+        )
+    });
+    let ranks = (1..8).map(|rank| {
+        format!(
+            r#"<text x="{}" y="{}" font-size="{}" font-family="sans-serif" text-anchor="middle" dominant-baseline="middle" fill="{}">{}</text>"#,
+            0.4 * unit, (8 - rank) as f64 * unit, font_size, colour, rank
+        )
+    });
+
+    files.chain(ranks).collect()
+}
+
+// a margin of one unit is left round the 7 x 7 grid for the labels; the hand and captured
+// men have no position
+fn get_position(location: Location, unit: f64) -> Option<(f64, f64)> {
+    let (file, rank) = location.get_coordinates()?;
+    Some(((file as f64 + 1.0) * unit, (7.0 - rank as f64) * unit))
+}
+
+fn escape(value: &str) -> String {
+    value.replace('&', "&amp;").replace('"', "&quot;").replace('<', "&lt;").replace('>', "&gt;")
+}
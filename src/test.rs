@@ -1,4 +1,17 @@
-use super::{Game, Player, GameMove, Location, Piece, GameStatus};
+#![allow(clippy::bool_assert_comparison)]
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use serde_json::{self, Value};
+use super::{Game, Player, GameMove, Location, Piece, GameStatus, Variant, RuleSet, BlockedOutcome};
+use super::{Board, STANDARD_BOARD, MORABARABA_BOARD, MoveError, GameAction, EndReason};
+use super::{GameEvent, Phase};
+use super::{Symmetry, FEATURE_COUNT, ACTION_COUNT, OpeningBook};
+use super::{HintReason, Threat};
+use std::sync::Mutex;
+use super::svg::{self, SvgOptions};
+use super::{Clock, TimeControl, TimeSource};
 
 #[test]
 fn new_games_have_18_pieces() {
@@ -150,7 +163,7 @@ fn when_the_removal_results_in_the_opponent_having_less_than_3_pieces_the_game_e
     let mut game = Game::load(get_completable_game()).unwrap();
     let new_move = game_move!(One, F4, G4, D7);
     assert!(game.submit(new_move));
-    assert_eq!(game.get_status(), GameStatus::Win(Player::One));
+    assert_eq!(game.get_status(), GameStatus::Win(Player::One, EndReason::MillCapture));
 }
 
 #[test]
@@ -184,124 +197,1654 @@ fn it_can_export_to_json() {
     assert_eq!(game.get_json(), robot_json);
 }
 
-// ------------------------------------------------------------------------------------------------
+#[test]
+fn morabaraba_games_have_24_pieces() {
+    let game = Game::with_variant(Variant::Morabaraba);
+    assert_eq!(game.get_pieces().len(), 24);
+}
 
-fn assert_piece_exists(pieces: &Vec<Piece>, player: Player, location: Location) {
-    assert!(pieces.iter().any(|&x| {
-        x.get_location() == location && x.get_player() == player
-    }), "piece doesn't seem to exist");
+#[test]
+fn a_diagonal_line_forms_a_mill_in_morabaraba() {
+    let mut game = Game::with_variant(Variant::Morabaraba);
+    game.submit(game_move!(One, Hand, A7));
+    game.submit(game_move!(Two, Hand, D1));
+    game.submit(game_move!(One, Hand, B6));
+    game.submit(game_move!(Two, Hand, D2));
+    assert!(game.submit(game_move!(One, Hand, C5, D1)));
+    let pieces = game.get_pieces();
+    assert_piece_doesnt_exist(&pieces, Player::Two, Location::D1);
 }
 
-fn assert_piece_doesnt_exist(pieces: &Vec<Piece>, player: Player, location: Location) {
-    assert_eq!(pieces.iter().any(|&x| {
-        x.get_location() == location && x.get_player() == player
-    }), false, "piece exists");
+#[test]
+fn a_diagonal_line_isnt_a_mill_in_nine_mens_morris() {
+    let mut game = Game::new();
+    game.submit(game_move!(One, Hand, A7));
+    game.submit(game_move!(Two, Hand, D1));
+    game.submit(game_move!(One, Hand, B6));
+    game.submit(game_move!(Two, Hand, D2));
+    game.submit(game_move!(One, Hand, C5, D1));
+    let pieces = game.get_pieces();
+    assert_piece_exists(&pieces, Player::Two, Location::D1);
 }
 
-fn get_json() -> &'static str {
-    r#"{
-        "pieces": [
-            { "location": "A7", "player": 1 },
-            { "location": "A1", "player": 1 },
-            { "location": "G1", "player": 1 },
-            { "location": "G7", "player": 1 },
-            { "location": "B6", "player": 1 },
-            { "location": "E4", "player": 1 },
-            { "location": "F4", "player": 1 },
-            { "location": "F2", "player": 1 },
-            { "location": "C5", "player": 1 },
-            { "location": "F6", "player": 2 },
-            { "location": "B4", "player": 2 },
-            { "location": "D7", "player": 2 },
-            { "location": "D1", "player": 2 },
-            { "location": "A4", "player": 2 },
-            { "location": "D5", "player": 2 },
-            { "location": "D2", "player": 2 },
-            { "location": "D6", "player": 2 },
-            { "location": "E3", "player": 2 }
-        ],
-        "player_turn": 1,
-        "turn": 2
-    }"#
+#[test]
+fn pieces_can_move_along_diagonals_in_morabaraba() {
+    let mut game = Game::load(get_morabaraba_game()).unwrap();
+    assert!(game.submit(game_move!(One, A1, B2)));
 }
 
-fn get_late_game() -> &'static str {
-    r#"{
-        "pieces": [
-            { "player": 1, "location": "A7"},
-            { "player": 1, "location": "A1"},
-            { "player": 1, "location": "G1"},
-            { "player": 1, "location": "G7"},
-            { "player": 1, "location": "B6"},
-            { "player": 1, "location": "E4"},
-            { "player": 1, "location": "Captured"},
-            { "player": 1, "location": "Captured"},
-            { "player": 1, "location": "Captured"},
-            { "player": 2, "location": "F6"},
-            { "player": 2, "location": "B4"},
-            { "player": 2, "location": "D7"},
-            { "player": 2, "location": "D1"},
-            { "player": 2, "location": "A4"},
-            { "player": 2, "location": "Captured"},
-            { "player": 2, "location": "Captured"},
-            { "player": 2, "location": "Captured"},
-            { "player": 2, "location": "Captured"}
-        ],
-        "turn": 2,
-        "player_turn": 1
-    }"#
+#[test]
+fn pieces_cant_move_along_diagonals_in_nine_mens_morris() {
+    let mut game = Game::load(get_late_game()).unwrap();
+    assert_eq!(game.submit(game_move!(One, B6, C5)), false);
 }
 
-fn get_completable_game() -> &'static str {
-    r#"{
-        "pieces": [
-            { "player": 1, "location": "A7"},
-            { "player": 1, "location": "A1"},
-            { "player": 1, "location": "G1"},
-            { "player": 1, "location": "G7"},
-            { "player": 1, "location": "F4"},
-            { "player": 1, "location": "E4"},
-            { "player": 1, "location": "Captured"},
-            { "player": 1, "location": "Captured"},
-            { "player": 1, "location": "Captured"},
-            { "player": 2, "location": "F6"},
-            { "player": 2, "location": "Captured"},
-            { "player": 2, "location": "D7"},
-            { "player": 2, "location": "D1"},
-            { "player": 2, "location": "Captured"},
-            { "player": 2, "location": "Captured"},
-            { "player": 2, "location": "Captured"},
-            { "player": 2, "location": "Captured"},
-            { "player": 2, "location": "Captured"}
-        ],
-        "turn": 2,
-        "player_turn": 1
-    }"#
+#[test]
+fn a_player_with_three_pieces_can_fly_in_morabaraba() {
+    let mut game = Game::load(get_morabaraba_flying_game()).unwrap();
+    assert!(game.submit(game_move!(One, A1, E4)));
 }
 
-fn get_existing_mill() -> &'static str {
-    r#"{
-        "pieces": [
-            { "player": 1, "location": "A7"},
-            { "player": 1, "location": "A1"},
-            { "player": 1, "location": "G1"},
-            { "player": 1, "location": "G7"},
-            { "player": 1, "location": "F4"},
-            { "player": 1, "location": "E4"},
-            { "player": 1, "location": "Captured"},
-            { "player": 1, "location": "Captured"},
-            { "player": 1, "location": "Captured"},
-            { "player": 2, "location": "F6"},
-            { "player": 2, "location": "D3"},
-            { "player": 2, "location": "D7"},
-            { "player": 2, "location": "D1"},
-            { "player": 2, "location": "D2"},
-            { "player": 2, "location": "Captured"},
-            { "player": 2, "location": "Captured"},
-            { "player": 2, "location": "Captured"},
-            { "player": 2, "location": "Captured"}
-        ],
-        "turn": 2,
-        "player_turn": 1
-    }"#
+#[test]
+fn a_player_with_four_pieces_cant_fly_in_morabaraba() {
+    let mut game = Game::load(get_morabaraba_game()).unwrap();
+    assert_eq!(game.submit(game_move!(One, A1, E5)), false);
+}
+
+#[test]
+fn a_mill_reformed_on_the_next_move_doesnt_capture_in_morabaraba() {
+    let mut game = Game::load(get_morabaraba_game()).unwrap();
+    assert!(game.submit(game_move!(One, A4, B4)));
+    assert!(game.submit(game_move!(Two, F6, F4)));
+    assert_eq!(game.submit(game_move!(One, B4, A4, D7)), false);
+    assert!(game.submit(game_move!(One, B4, A4)));
+    let pieces = game.get_pieces();
+    assert_piece_exists(&pieces, Player::Two, Location::D7);
+}
+
+#[test]
+fn a_mill_reformed_later_captures_in_morabaraba() {
+    let mut game = Game::load(get_morabaraba_game()).unwrap();
+    assert!(game.submit(game_move!(One, A4, B4)));
+    assert!(game.submit(game_move!(Two, F6, F4)));
+    assert!(game.submit(game_move!(One, E4, E5)));
+    assert!(game.submit(game_move!(Two, F4, F6)));
+    assert!(game.submit(game_move!(One, B4, A4, D7)));
+    let pieces = game.get_pieces();
+    assert_piece_doesnt_exist(&pieces, Player::Two, Location::D7);
+}
+
+#[test]
+fn a_mill_reformed_on_the_next_move_captures_in_nine_mens_morris() {
+    let morabaraba = get_morabaraba_game();
+    let mut game = Game::load(&morabaraba.replace("Morabaraba", "NineMensMorris")).unwrap();
+    assert!(game.submit(game_move!(One, A4, B4)));
+    assert!(game.submit(game_move!(Two, F6, F4)));
+    assert!(game.submit(game_move!(One, B4, A4, D7)));
+    let pieces = game.get_pieces();
+    assert_piece_doesnt_exist(&pieces, Player::Two, Location::D7);
+}
+
+#[test]
+fn morabaraba_is_won_by_reducing_the_opponent_to_two_pieces() {
+    let mut game = Game::load(get_morabaraba_flying_game()).unwrap();
+    assert_eq!(game.get_status(), GameStatus::Playing);
+    assert!(game.submit(game_move!(One, A1, B6)));
+    assert!(game.submit(game_move!(Two, D7, D6)));
+    assert!(game.submit(game_move!(One, A4, C5, D6)));
+    assert!(game.submit(game_move!(Two, D1, D2)));
+    assert!(game.submit(game_move!(One, C5, A4)));
+    assert!(game.submit(game_move!(Two, F6, F4)));
+    assert!(game.submit(game_move!(One, B6, A1, D2)));
+    assert_eq!(game.get_status(), GameStatus::Win(Player::One, EndReason::MillCapture));
+}
+
+#[test]
+fn the_variant_is_saved_with_the_game() {
+    let game = Game::with_variant(Variant::Morabaraba);
+    let loaded_game = Game::load(&game.get_json()).unwrap();
+    assert_eq!(loaded_game.get_variant(), Variant::Morabaraba);
+    assert_eq!(loaded_game.get_pieces().len(), 24);
+}
+
+#[test]
+fn saves_from_before_house_rules_still_load() {
+    let game = Game::load(get_old_save()).unwrap();
+    assert_eq!(game.get_variant(), Variant::Morabaraba);
+    assert_eq!(game.get_history(), vec!(
+        game_move!(One, Hand, A7),
+        game_move!(Two, Hand, D1),
+        game_move!(One, Hand, B6, D1)
+    ));
+}
+
+#[test]
+fn an_unknown_variant_is_an_error_when_loading() {
+    let game_json = with_rules(get_json(), json!({ "variant": "Lasca" }));
+    assert!(Game::load(&game_json).is_err());
+}
+
+#[test]
+fn a_save_that_isnt_a_game_is_an_error_when_loading() {
+    assert!(Game::load("{}").is_err());
+    assert!(Game::load("[]").is_err());
+
+    let broken = |key: &str, value: Value| {
+        let mut game: Value = serde_json::from_str(get_json()).unwrap();
+        game[key] = value;
+        Game::load(&game.to_string()).is_err()
+    };
+    assert!(broken("pieces", json!([{ "player": 1, "location": "Z9" }])));
+    assert!(broken("pieces", json!([{ "player": 3, "location": "Hand" }])));
+    assert!(broken("player_turn", json!("One")));
+    assert!(broken("turn", json!(null)));
+    assert!(broken("result", json!({ "status": "Win", "player": 1, "reason": "Boredom" })));
+    assert!(broken("clock", json!({ "control": "Fischer", "base": 60000 })));
+    assert!(broken("rules", json!({ "variant": "NineMensMorris", "blocked": "Shrug" })));
+}
+
+#[test]
+fn the_move_history_is_saved_with_the_game() {
+    let mut game = Game::load(get_morabaraba_game()).unwrap();
+    game.submit(game_move!(One, A4, B4));
+    let loaded_game = Game::load(&game.get_json()).unwrap();
+    assert_eq!(loaded_game.get_history(), vec!(game_move!(One, A4, B4)));
+}
+
+#[test]
+fn pieces_in_a_mill_can_be_removed_when_the_rules_allow_it() {
+    let game_json = with_rules(get_existing_mill(), json!({ "capture_from_mills": true }));
+    let mut game = Game::load(&game_json).unwrap();
+    assert!(game.submit(game_move!(One, F4, G4, D3)));
+    let pieces = game.get_pieces();
+    assert_piece_doesnt_exist(&pieces, Player::Two, Location::D3);
+}
+
+#[test]
+fn a_player_with_three_pieces_can_fly() {
+    let mut game = Game::load(get_flying_game()).unwrap();
+    assert!(game.submit(game_move!(One, A1, E4)));
+}
+
+#[test]
+fn a_player_with_three_pieces_cant_fly_when_flying_is_disabled() {
+    let game_json = with_rules(get_flying_game(), json!({ "flying": false }));
+    let mut game = Game::load(&game_json).unwrap();
+    assert_eq!(game.submit(game_move!(One, A1, E4)), false);
+}
+
+#[test]
+fn a_blocked_player_loses_by_default() {
+    let game = Game::load(get_blocked_game()).unwrap();
+    assert_eq!(game.get_status(), GameStatus::Win(Player::Two, EndReason::Blockade));
+}
+
+#[test]
+fn a_blocked_player_draws_when_the_rules_say_so() {
+    let game_json = with_rules(get_blocked_game(), json!({ "blocked": "Draw" }));
+    let game = Game::load(&game_json).unwrap();
+    assert_eq!(game.get_status(), GameStatus::Draw(EndReason::Blockade));
+}
+
+#[test]
+fn a_player_who_can_fly_isnt_blocked() {
+    let game = Game::load(get_flying_game()).unwrap();
+    assert_eq!(game.get_status(), GameStatus::Playing);
+}
+
+#[test]
+fn house_rules_are_saved_with_the_game() {
+    let rules = RuleSet {
+        capture_from_mills: true,
+        flying: false,
+        blocked: BlockedOutcome::Draw,
+        ..RuleSet::morabaraba()
+    };
+    let game = Game::with_rules(rules);
+    let loaded_game = Game::load(&game.get_json()).unwrap();
+    assert_eq!(loaded_game.get_rules(), rules);
+}
+
+#[test]
+fn closing_two_mills_captures_two_pieces_when_the_rules_allow_it() {
+    let mut game = Game::with_rules(RuleSet { capture_per_mill: true, ..RuleSet::standard() });
+    play_into_double_mill(&mut game);
+    assert!(game.submit(game_move!(One, Hand, A4, G4, G1)));
+    let pieces = game.get_pieces();
+    assert_piece_doesnt_exist(&pieces, Player::Two, Location::G4);
+    assert_piece_doesnt_exist(&pieces, Player::Two, Location::G1);
+}
+
+#[test]
+fn closing_two_mills_captures_one_piece_by_default() {
+    let mut game = Game::new();
+    play_into_double_mill(&mut game);
+    assert_eq!(game.submit(game_move!(One, Hand, A4, G4, G1)), false);
+    assert!(game.submit(game_move!(One, Hand, A4, G4)));
+}
+
+#[test]
+fn closing_one_mill_captures_one_piece_when_double_mills_capture_two() {
+    let game_json = with_rules(get_json(), json!({ "capture_per_mill": true }));
+    let mut game = Game::load(&game_json).unwrap();
+    assert_eq!(game.submit(game_move!(One, F4, G4, D2, D1)), false);
+}
+
+#[test]
+fn a_piece_cant_be_removed_without_closing_a_mill() {
+    let mut game = Game::new();
+    assert_eq!(game.submit(game_move!(One, Hand, A7, A4)), false);
+}
+
+#[test]
+fn a_second_capture_can_come_from_a_mill_once_the_first_took_the_last_man_outside_one() {
+    let mut game = Game::load(get_double_mill_game()).unwrap();
+    assert_eq!(game.validate(&game_move!(One, Hand, A4, D2, G7)), Err(MoveError::InvalidRemoval));
+    assert!(game.submit(game_move!(One, Hand, A4, G7, D2)));
+    let pieces = game.get_pieces();
+    assert_piece_doesnt_exist(&pieces, Player::Two, Location::G7);
+    assert_piece_doesnt_exist(&pieces, Player::Two, Location::D2);
+}
+
+#[test]
+fn closing_a_mill_has_to_take_a_man() {
+    let mut game = Game::new();
+    game.submit(game_move!(One, Hand, A7));
+    game.submit(game_move!(Two, Hand, D1));
+    game.submit(game_move!(One, Hand, A4));
+    game.submit(game_move!(Two, Hand, D2));
+    assert_eq!(game.validate(&game_move!(One, Hand, A1)), Err(MoveError::MissingRemoval));
+    assert!(!game.get_legal_moves().contains(&game_move!(One, Hand, A1)));
+    assert!(game.submit(game_move!(One, Hand, A1, D1)));
+}
+
+#[test]
+fn the_hand_cant_be_removed() {
+    let mut game = Game::new();
+    game.submit(game_move!(One, Hand, A7));
+    game.submit(game_move!(Two, Hand, D1));
+    game.submit(game_move!(One, Hand, A4));
+    game.submit(game_move!(Two, Hand, D2));
+    assert_eq!(game.submit(game_move!(One, Hand, A1, Hand)), false);
+    assert_eq!(game.get_status(), GameStatus::Playing);
+    assert!(game.get_pieces().iter().all(|piece| piece.get_location() != Location::Captured));
+}
+
+#[test]
+fn an_empty_point_cant_be_removed() {
+    let mut game = Game::load(get_completable_game()).unwrap();
+    assert_eq!(game.submit(game_move!(One, F4, G4, B6)), false);
+}
+
+#[test]
+fn a_player_cant_remove_their_own_man() {
+    let mut game = Game::load(get_completable_game()).unwrap();
+    assert_eq!(game.submit(game_move!(One, F4, G4, A7)), false);
+}
+
+#[test]
+fn the_same_piece_cant_be_removed_twice() {
+    let mut game = Game::with_rules(RuleSet { capture_per_mill: true, ..RuleSet::standard() });
+    play_into_double_mill(&mut game);
+    assert_eq!(game.submit(game_move!(One, Hand, A4, G4, G4)), false);
+}
+
+#[test]
+fn a_piece_cant_slide_past_the_middle_of_a_line() {
+    let mut game = Game::load(get_late_game()).unwrap();
+    assert_eq!(game.submit(game_move!(One, E4, G4)), false);
+    assert!(game.submit(game_move!(One, E4, F4)));
+}
+
+#[test]
+fn the_standard_board_has_24_points_32_edges_and_16_mills() {
+    assert_eq!(STANDARD_BOARD.get_points().len(), 24);
+    assert_eq!(STANDARD_BOARD.get_edges().len(), 32);
+    assert_eq!(STANDARD_BOARD.get_mills().len(), 16);
+}
+
+#[test]
+fn the_morabaraba_board_adds_the_diagonals() {
+    assert!(MORABARABA_BOARD.is_adjacent(Location::B6, Location::A7));
+    assert!(!STANDARD_BOARD.is_adjacent(Location::B6, Location::A7));
+    assert_eq!(MORABARABA_BOARD.get_mills().len(), 20);
+}
+
+#[test]
+fn board_mill_rows_match_location_rows() {
+    for location in Location::get_points() {
+        let mut rows = STANDARD_BOARD.get_rows(location);
+        let mut expected = Location::get_rows(location).unwrap();
+        rows.iter_mut().chain(expected.iter_mut()).for_each(|row| {
+            if row.0.to_str() > row.1.to_str() {
+                *row = (row.1, row.0);
+            }
+        });
+        rows.sort_by_key(|row| (row.0.to_str(), row.1.to_str()));
+        expected.sort_by_key(|row| (row.0.to_str(), row.1.to_str()));
+        assert_eq!(rows, expected, "{:?}", location);
+    }
+}
+
+#[test]
+fn a_board_can_be_built_from_text() {
+    let board = Board::from_text(get_small_board()).unwrap();
+    assert_eq!(board.get_points().len(), 4);
+    assert!(board.is_adjacent(Location::D5, Location::A7));
+    assert_eq!(board.get_rows(Location::A7), vec!((Location::D7, Location::G7)));
+}
+
+#[test]
+fn a_board_with_unknown_points_is_rejected() {
+    assert!(Board::from_text("points: A7 Z9").is_err());
+    assert!(Board::from_text("points: A7\nedges: A7-D7").is_err());
+    assert!(Board::from_text("points: A7 D7\nedges: A7-D7-G7").is_err());
+    assert!(Board::from_text("points: A7 D7\nmills: A7-A7-D7").is_err());
+}
+
+#[test]
+fn a_board_can_be_built_from_json() {
+    let board = Board::from_json(&STANDARD_BOARD.get_json()).unwrap();
+    assert_eq!(board, STANDARD_BOARD);
+}
+
+#[test]
+fn pieces_cant_be_placed_off_a_custom_board() {
+    let board = Board::from_text(get_small_board()).unwrap();
+    let mut game = Game::with_board(RuleSet::standard(), board);
+    assert_eq!(game.submit(game_move!(One, Hand, A1)), false);
+    assert!(game.submit(game_move!(One, Hand, A7)));
+}
+
+#[test]
+fn a_custom_board_is_saved_with_the_game() {
+    let board = Board::from_text(get_small_board()).unwrap();
+    let game = Game::with_board(RuleSet::standard(), board.clone());
+    let loaded_game = Game::load(&game.get_json()).unwrap();
+    assert_eq!(loaded_game.get_board(), board);
+}
+
+#[test]
+fn a_malformed_custom_board_is_an_error_when_loading() {
+    let mut game: Value = serde_json::from_str(get_json()).unwrap();
+    game["board"] = json!({ "points": ["A7", "D7"], "edges": [["A7"]], "mills": [] });
+    assert!(Game::load(&game.to_string()).is_err());
+    game["board"] = json!({
+        "points": ["A7", "D7"],
+        "edges": [["A7", "D7"]],
+        "mills": [["A7", "A7", "D7"]]
+    });
+    assert!(Game::load(&game.to_string()).is_err());
+    game["board"] = json!({ "points": ["A7", "D7"], "edges": [["A7", "A7"]], "mills": [] });
+    assert!(Game::load(&game.to_string()).is_err());
+}
+
+#[test]
+fn neighbors_are_mutual() {
+    for location in Location::get_points() {
+        for neighbor in location.neighbors() {
+            assert!(neighbor.neighbors().contains(&location), "{:?} {:?}", location, neighbor);
+        }
+    }
+}
+
+#[test]
+fn hand_and_captured_have_no_neighbors() {
+    assert!(Location::Hand.neighbors().is_empty());
+    assert!(Location::Captured.neighbors().is_empty());
+}
+
+#[test]
+fn a_piece_can_only_slide_to_its_neighbors() {
+    let points = Location::get_points();
+    for &from in points.iter() {
+        let expected = get_standard_neighbors(from);
+        let neighbors = from.neighbors();
+        assert_eq!(neighbors.len(), expected.len(), "{:?}", from);
+        assert!(neighbors.iter().all(|neighbor| expected.contains(neighbor)), "{:?}", from);
+
+        for &to in points.iter().filter(|&&to| to != from) {
+            let mut game = Game::load(&get_sliding_game(from, to)).unwrap();
+            // a slide that closes a mill has to take a man as well
+            let legal = game.get_legal_moves().into_iter().find(|game_move| {
+                game_move.get_from() == from && game_move.get_to() == to
+            });
+            assert_eq!(
+                legal.is_some_and(|game_move| game.submit(game_move)),
+                expected.contains(&to),
+                "{:?} to {:?}", from, to
+            );
+        }
+    }
+}
+
+#[test]
+fn a_new_game_is_drawn_as_the_empty_board() {
+    let game = Game::new();
+    let expected = [
+        "7 o-----------o-----------o",
+        "  |           |           |",
+        "6 |   o-------o-------o   |",
+        "  |   |       |       |   |",
+        "5 |   |   o---o---o   |   |",
+        "  |   |   |       |   |   |",
+        "4 o---o---o       o---o---o",
+        "  |   |   |       |   |   |",
+        "3 |   |   o---o---o   |   |",
+        "  |   |       |       |   |",
+        "2 |   o-------o-------o   |",
+        "  |           |           |",
+        "1 o-----------o-----------o",
+        "  a   b   c   d   e   f   g",
+        "",
+        "player 1: 9 in hand, 0 captured",
+        "player 2: 9 in hand, 0 captured",
+        "player 1 to move",
+        ""
+    ].join("\n");
+    assert_eq!(game.to_string(), expected);
+}
+
+#[test]
+fn men_and_the_last_move_are_drawn_on_the_board() {
+    let mut game = Game::new();
+    game.submit(game_move!(One, Hand, A7));
+    game.submit(game_move!(Two, Hand, D7));
+    let drawing = game.to_string();
+    let lines: Vec<&str> = drawing.lines().collect();
+    assert_eq!(lines[0], "7 1----------(2)----------o");
+    assert_eq!(lines[15], "player 1: 8 in hand, 0 captured");
+    assert_eq!(lines[16], "player 2: 8 in hand, 0 captured");
+}
+
+#[test]
+fn a_selected_man_is_drawn_with_where_it_can_move() {
+    let game = Game::load(get_late_game()).unwrap();
+    let drawing = game.render_ascii(Some(Location::E4));
+    let lines: Vec<&str> = drawing.lines().collect();
+    assert_eq!(lines[4], "5 |   |   o---o---*   |   |");
+    assert_eq!(lines[6], "4 2---2---o      [1]--*---o");
+    assert_eq!(lines[8], "3 |   |   o---o---*   |   |");
+}
+
+#[test]
+fn captures_and_the_winner_are_reported() {
+    let mut game = Game::load(get_completable_game()).unwrap();
+    game.submit(game_move!(One, F4, G4, D7));
+    let drawing = game.to_string();
+    let lines: Vec<&str> = drawing.lines().collect();
+    assert_eq!(lines[16], "player 2: 0 in hand, 7 captured");
+    assert_eq!(lines[17], "player 1 wins");
+}
+
+#[test]
+fn morabaraba_diagonals_are_drawn() {
+    let game = Game::with_variant(Variant::Morabaraba);
+    let drawing = game.to_string();
+    let lines: Vec<&str> = drawing.lines().collect();
+    assert_eq!(lines[1], "  | \\         |         / |");
+    assert_eq!(lines[11], "  | /         |         \\ |");
+}
+
+#[test]
+fn an_svg_drawing_is_a_standalone_document() {
+    let drawing = svg::render(&Game::new(), &SvgOptions::default());
+    assert!(drawing.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(drawing.trim_end().ends_with("</svg>"));
+}
+
+#[test]
+fn an_svg_drawing_has_every_point_line_and_man() {
+    let game = Game::load(get_late_game()).unwrap();
+    let drawing = svg::render(&game, &SvgOptions::default());
+    assert_eq!(drawing.matches("class=\"point\"").count(), 24);
+    assert_eq!(drawing.matches("<line").count(), 32);
+    assert_eq!(drawing.matches("class=\"piece\"").count(), 11);
+}
+
+#[test]
+fn an_svg_drawing_uses_the_chosen_size_and_colours() {
+    let options = SvgOptions {
+        size: 800,
+        player_one: String::from("red"),
+        player_two: String::from("blue"),
+        ..SvgOptions::default()
+    };
+    let game = Game::load(get_late_game()).unwrap();
+    let drawing = svg::render(&game, &options);
+    assert!(drawing.contains("width=\"800\" height=\"800\""));
+    assert_eq!(drawing.matches("fill=\"red\"").count(), 6);
+    assert_eq!(drawing.matches("fill=\"blue\"").count(), 5);
+}
+
+#[test]
+fn svg_coordinate_labels_can_be_left_out() {
+    let game = Game::new();
+    let labelled = svg::render(&game, &SvgOptions::default());
+    let unlabelled = svg::render(&game, &SvgOptions { labels: false, ..SvgOptions::default() });
+    assert_eq!(labelled.matches("<text").count(), 14);
+    assert!(labelled.contains(">g</text>"));
+    assert_eq!(unlabelled.matches("<text").count(), 0);
+}
+
+#[test]
+fn an_svg_drawing_can_show_a_move_and_its_removals() {
+    let game = Game::load(get_json()).unwrap();
+    let drawing = svg::render_with_move(&game, Some(&game_move!(One, F4, G4, D2)), &SvgOptions::default());
+    assert_eq!(drawing.matches("class=\"arrow\"").count(), 1);
+    assert_eq!(drawing.matches("class=\"removal\"").count(), 1);
+}
+
+#[test]
+fn an_svg_drawing_shows_a_placement_as_a_ring() {
+    let game = Game::new();
+    let drawing = svg::render_with_move(&game, Some(&game_move!(One, Hand, A7)), &SvgOptions::default());
+    assert_eq!(drawing.matches("class=\"placement\"").count(), 1);
+    assert_eq!(drawing.matches("class=\"arrow\"").count(), 0);
+}
+
+#[test]
+fn an_svg_drawing_skips_parts_of_a_move_that_are_off_the_board() {
+    let game = Game::new();
+    let options = SvgOptions::default();
+    let drawing = svg::render_with_move(&game, Some(&game_move!(One, A7, Captured, Hand)), &options);
+    assert_eq!(drawing.matches("class=\"arrow\"").count(), 0);
+    assert_eq!(drawing.matches("class=\"removal\"").count(), 0);
+
+    let drawing = svg::render_with_move(&game, Some(&game_move!(One, A7, A7)), &options);
+    assert_eq!(drawing.matches("class=\"arrow\"").count(), 0);
+    assert!(!drawing.contains("NaN"));
+}
+
+#[test]
+fn svg_colours_are_escaped() {
+    let options = SvgOptions { background: String::from("\"><script>"), ..SvgOptions::default() };
+    let drawing = svg::render(&Game::new(), &options);
+    assert!(!drawing.contains("<script>"));
+}
+
+#[test]
+fn locations_know_their_place_on_the_diagram() {
+    assert_eq!(Location::A1.get_coordinates(), Some((0, 0)));
+    assert_eq!(Location::D6.get_coordinates(), Some((3, 5)));
+    assert_eq!(Location::G7.get_coordinates(), Some((6, 6)));
+    assert_eq!(Location::Hand.get_coordinates(), None);
+}
+
+#[test]
+fn validate_explains_why_a_move_is_rejected() {
+    let mut game = Game::new();
+    assert_eq!(game.validate(&game_move!(Two, Hand, A7)), Err(MoveError::NotYourTurn));
+    assert_eq!(game.validate(&game_move!(One, A7, A4)), Err(MoveError::NoManToMove));
+    game.submit(game_move!(One, Hand, A7));
+    assert_eq!(game.validate(&game_move!(Two, Hand, A7)), Err(MoveError::Occupied));
+    assert_eq!(game.validate(&game_move!(Two, Hand, A4, A7)), Err(MoveError::TooManyRemovals));
+}
+
+#[test]
+fn validate_explains_rejected_moves_later_in_the_game() {
+    let game = Game::load(get_late_game()).unwrap();
+    assert_eq!(game.validate(&game_move!(One, E4, G4)), Err(MoveError::NotAdjacent));
+    assert_eq!(game.validate(&game_move!(One, E4, F4)), Ok(()));
+
+    let game = Game::load(get_existing_mill()).unwrap();
+    assert_eq!(game.validate(&game_move!(One, F4, G4, D3)), Err(MoveError::InvalidRemoval));
+}
+
+#[test]
+fn validate_rejects_removals_that_arent_the_opponents_men() {
+    let game = Game::load(get_completable_game()).unwrap();
+    assert_eq!(game.validate(&game_move!(One, F4, G4, A7)), Err(MoveError::InvalidRemoval));
+    assert_eq!(game.validate(&game_move!(One, F4, G4, B6)), Err(MoveError::InvalidRemoval));
+    assert_eq!(game.validate(&game_move!(One, F4, G4, Hand)), Err(MoveError::InvalidRemoval));
+    assert_eq!(game.validate(&game_move!(One, F4, G4, Captured)), Err(MoveError::InvalidRemoval));
+    assert_eq!(game.validate(&game_move!(One, F4, G4, D7)), Ok(()));
+}
+
+#[test]
+fn a_player_cant_move_a_man_they_dont_have() {
+    let mut game = Game::load(get_late_game()).unwrap();
+    assert_eq!(game.submit(game_move!(One, F6, F4)), false);
+    assert_eq!(game.submit(game_move!(One, Hand, F4)), false);
+}
+
+#[test]
+fn moves_can_be_read_from_coordinates() {
+    assert_eq!(GameMove::from_notation(Player::One, "a7"), Some(game_move!(One, Hand, A7)));
+    assert_eq!(GameMove::from_notation(Player::Two, "C5-c4"), Some(game_move!(Two, C5, C4)));
+    assert_eq!(
+        GameMove::from_notation(Player::One, "a4xg4xG1"),
+        Some(game_move!(One, Hand, A4, G4, G1))
+    );
+    assert_eq!(GameMove::from_notation(Player::One, "f4-g4xd2"), Some(game_move!(One, F4, G4, D2)));
+    assert_eq!(GameMove::from_notation(Player::One, "h8"), None);
+    assert_eq!(GameMove::from_notation(Player::One, "a7-a4-a1"), None);
+    assert_eq!(GameMove::from_notation(Player::One, "a7x"), None);
+}
+
+#[test]
+fn moves_are_written_as_coordinates() {
+    assert_eq!(game_move!(One, Hand, A7).to_string(), "a7");
+    assert_eq!(game_move!(One, F4, G4, D2).to_string(), "f4-g4xd2");
+    assert_eq!(game_move!(One, Hand, A4, G4, G1).to_string(), "a4xg4xg1");
+}
+
+#[test]
+fn sudden_death_clocks_run_down_by_the_time_taken() {
+    let time = Arc::new(FakeTime::default());
+    let mut game = Game::new();
+    game.set_clock(Clock::with_time_source(TimeControl::SuddenDeath(secs(60)), time.clone()));
+    time.advance(secs(3));
+    game.submit(game_move!(One, Hand, A7));
+    time.advance(secs(5));
+
+    let clock = game.get_clock().unwrap();
+    assert_eq!(clock.get_time_left(Player::One, false), secs(57));
+    assert_eq!(clock.get_time_left(Player::Two, true), secs(55));
+    assert_eq!(clock.get_move_times(), vec!(secs(3)));
+}
+
+#[test]
+fn fischer_clocks_add_the_increment_after_each_move() {
+    let time = Arc::new(FakeTime::default());
+    let mut game = Game::new();
+    game.set_clock(Clock::with_time_source(TimeControl::Fischer(secs(60), secs(5)), time.clone()));
+    time.advance(secs(3));
+    game.submit(game_move!(One, Hand, A7));
+    assert_eq!(game.get_clock().unwrap().get_time_left(Player::One, false), secs(62));
+}
+
+#[test]
+fn bronstein_clocks_give_back_up_to_the_delay_after_each_move() {
+    let time = Arc::new(FakeTime::default());
+    let mut game = Game::new();
+    game.set_clock(Clock::with_time_source(TimeControl::Bronstein(secs(60), secs(5)), time.clone()));
+    time.advance(secs(3));
+    game.submit(game_move!(One, Hand, A7));
+    time.advance(secs(8));
+    game.submit(game_move!(Two, Hand, D7));
+
+    let clock = game.get_clock().unwrap();
+    assert_eq!(clock.get_time_left(Player::One, false), secs(60));
+    assert_eq!(clock.get_time_left(Player::Two, false), secs(57));
+}
+
+#[test]
+fn bronstein_clocks_run_from_the_start_of_the_move() {
+    let time = Arc::new(FakeTime::default());
+    let mut game = Game::new();
+    game.set_clock(Clock::with_time_source(TimeControl::Bronstein(secs(60), secs(5)), time.clone()));
+    time.advance(secs(3));
+    assert_eq!(game.get_clock().unwrap().get_time_left(Player::One, true), secs(57));
+    time.advance(secs(59));
+    assert_eq!(game.get_status(), GameStatus::Win(Player::Two, EndReason::Timeout));
+}
+
+#[test]
+fn running_out_of_time_loses_the_game() {
+    let time = Arc::new(FakeTime::default());
+    let mut game = Game::new();
+    game.set_clock(Clock::with_time_source(TimeControl::SuddenDeath(secs(60)), time.clone()));
+    game.submit(game_move!(One, Hand, A7));
+    time.advance(secs(61));
+    assert_eq!(game.get_status(), GameStatus::Win(Player::One, EndReason::Timeout));
+    assert_eq!(game.validate(&game_move!(Two, Hand, D7)), Err(MoveError::GameOver));
+}
+
+#[test]
+fn the_clock_is_saved_with_the_game() {
+    let time = Arc::new(FakeTime::default());
+    let mut game = Game::new();
+    game.set_clock(Clock::with_time_source(TimeControl::Fischer(secs(60), secs(5)), time.clone()));
+    time.advance(secs(3));
+    game.submit(game_move!(One, Hand, A7));
+
+    let mut loaded_game = Game::load(&game.get_json()).unwrap();
+    loaded_game.set_time_source(time.clone());
+    assert_eq!(loaded_game.get_clock(), game.get_clock());
+    time.advance(secs(10));
+    assert_eq!(loaded_game.get_clock().unwrap().get_time_left(Player::Two, true), secs(50));
+}
+
+#[test]
+fn time_spent_on_the_running_turn_is_saved_with_the_game() {
+    let time = Arc::new(FakeTime::default());
+    let mut game = Game::new();
+    game.set_clock(Clock::with_time_source(TimeControl::SuddenDeath(secs(60)), time.clone()));
+    time.advance(secs(20));
+
+    let mut loaded_game = Game::load(&game.get_json()).unwrap();
+    loaded_game.set_time_source(time.clone());
+    assert_eq!(loaded_game.get_clock().unwrap().get_time_left(Player::One, true), secs(40));
+    time.advance(secs(5));
+    loaded_game.submit(game_move!(One, Hand, A7));
+    assert_eq!(loaded_game.get_clock().unwrap().get_time_left(Player::One, false), secs(35));
+}
+
+#[test]
+fn games_without_a_clock_are_saved_without_one() {
+    let game = Game::new();
+    assert!(!game.get_json().contains("clock"));
+    assert_eq!(Game::load(&game.get_json()).unwrap().get_clock(), None);
+}
+
+#[test]
+fn resigning_ends_the_game() {
+    let mut game = Game::new();
+    assert!(game.apply(GameAction::Resign(Player::One)));
+    assert_eq!(game.get_status(), GameStatus::Win(Player::Two, EndReason::Resignation));
+    assert_eq!(game.apply(GameAction::Move(game_move!(One, Hand, A7))), false);
+}
+
+#[test]
+fn an_accepted_draw_offer_ends_the_game() {
+    let mut game = Game::new();
+    assert!(game.apply(GameAction::OfferDraw(Player::One)));
+    assert_eq!(game.get_draw_offer(), Some(Player::One));
+    assert!(game.apply(GameAction::AcceptDraw(Player::Two)));
+    assert_eq!(game.get_status(), GameStatus::Draw(EndReason::Agreement));
+}
+
+#[test]
+fn players_cant_accept_their_own_draw_offer() {
+    let mut game = Game::new();
+    assert!(game.apply(GameAction::OfferDraw(Player::One)));
+    assert_eq!(game.apply(GameAction::AcceptDraw(Player::One)), false);
+    assert_eq!(game.get_status(), GameStatus::Playing);
+}
+
+#[test]
+fn a_declined_draw_offer_is_withdrawn() {
+    let mut game = Game::new();
+    assert!(game.apply(GameAction::OfferDraw(Player::One)));
+    assert!(game.apply(GameAction::DeclineDraw(Player::Two)));
+    assert_eq!(game.get_draw_offer(), None);
+    assert_eq!(game.apply(GameAction::AcceptDraw(Player::Two)), false);
+}
+
+#[test]
+fn moving_instead_of_answering_declines_a_draw_offer() {
+    let mut game = Game::new();
+    assert!(game.apply(GameAction::Move(game_move!(One, Hand, A7))));
+    assert!(game.apply(GameAction::OfferDraw(Player::One)));
+    assert!(game.apply(GameAction::Move(game_move!(Two, Hand, D7))));
+    assert_eq!(game.get_draw_offer(), None);
+}
+
+#[test]
+fn the_same_position_three_times_is_a_draw() {
+    let mut game = Game::load(&get_sliding_game(Location::C5, Location::D5)).unwrap();
+    for _ in 0..2 {
+        assert_eq!(game.get_status(), GameStatus::Playing);
+        assert!(game.submit(game_move!(One, C5, D5)));
+        assert!(game.submit(game_move!(Two, B6, D6)));
+        assert!(game.submit(game_move!(One, D5, C5)));
+        assert!(game.submit(game_move!(Two, D6, B6)));
+    }
+    assert_eq!(game.get_status(), GameStatus::Draw(EndReason::Repetition));
+}
+
+#[test]
+fn a_game_drawn_by_repetition_or_blockade_stays_drawn_when_the_clock_runs_out() {
+    let time = Arc::new(FakeTime::default());
+    let mut game = Game::load(&get_sliding_game(Location::C5, Location::D5)).unwrap();
+    game.set_clock(Clock::with_time_source(TimeControl::SuddenDeath(secs(60)), time.clone()));
+    for _ in 0..2 {
+        assert!(game.submit(game_move!(One, C5, D5)));
+        assert!(game.submit(game_move!(Two, B6, D6)));
+        assert!(game.submit(game_move!(One, D5, C5)));
+        assert!(game.submit(game_move!(Two, D6, B6)));
+    }
+    time.advance(secs(61));
+    assert_eq!(game.get_status(), GameStatus::Draw(EndReason::Repetition));
+
+    let game_json = with_rules(get_blocked_game(), json!({ "blocked": "Draw" }));
+    let mut game = Game::load(&game_json).unwrap();
+    game.set_clock(Clock::with_time_source(TimeControl::SuddenDeath(secs(60)), time.clone()));
+    time.advance(secs(61));
+    assert_eq!(game.get_status(), GameStatus::Draw(EndReason::Blockade));
+}
+
+#[test]
+fn repetition_doesnt_draw_when_the_rules_turn_it_off() {
+    let mut game_json: Value = serde_json::from_str(&get_sliding_game(Location::C5, Location::D5)).unwrap();
+    game_json["rules"]["threefold_repetition"] = json!(false);
+    let mut game = Game::load(&game_json.to_string()).unwrap();
+    for _ in 0..3 {
+        assert!(game.submit(game_move!(One, C5, D5)));
+        assert!(game.submit(game_move!(Two, B6, D6)));
+        assert!(game.submit(game_move!(One, D5, C5)));
+        assert!(game.submit(game_move!(Two, D6, B6)));
+    }
+    assert_eq!(game.get_status(), GameStatus::Playing);
+}
+
+#[test]
+fn the_turn_count_runs_past_255() {
+    let mut game_json: Value = serde_json::from_str(&get_sliding_game(Location::C5, Location::D5)).unwrap();
+    game_json["rules"]["threefold_repetition"] = json!(false);
+    let mut game = Game::load(&game_json.to_string()).unwrap();
+    let start = game.get_turn();
+    for _ in 0..70 {
+        assert!(game.submit(game_move!(One, C5, D5)));
+        assert!(game.submit(game_move!(Two, B6, D6)));
+        assert!(game.submit(game_move!(One, D5, C5)));
+        assert!(game.submit(game_move!(Two, D6, B6)));
+    }
+    assert_eq!(game.get_turn(), start + 280);
+    assert_eq!(Game::load(&game.get_json()).unwrap().get_turn(), start + 280);
+}
+
+#[test]
+fn actions_survive_a_round_trip_through_json() {
+    let actions = vec!(
+        GameAction::Move(game_move!(One, A4, B4, D7)),
+        GameAction::Resign(Player::Two),
+        GameAction::OfferDraw(Player::One),
+        GameAction::AcceptDraw(Player::Two),
+        GameAction::DeclineDraw(Player::Two)
+    );
+    for action in actions {
+        assert_eq!(GameAction::load(&action.get_json()).unwrap(), action);
+    }
+}
+
+#[test]
+fn malformed_actions_are_errors() {
+    assert!(GameAction::load(r#"{ "action": "Resign" }"#).is_err());
+    assert!(GameAction::load(r#"{ "player": 1 }"#).is_err());
+    assert!(GameAction::load(r#"{ "action": "Castle", "player": 1 }"#).is_err());
+    assert!(GameAction::load(r#"{ "action": "Move", "move": { "player": 1, "from": "Hand" } }"#).is_err());
+    assert!(GameAction::load(r#"{ "action": "Move", "move": { "player": 3, "from": "Hand", "to": "A7" } }"#).is_err());
+}
+
+#[test]
+fn the_result_and_draw_offer_are_saved_with_the_game() {
+    let mut game = Game::new();
+    game.apply(GameAction::OfferDraw(Player::One));
+    assert_eq!(Game::load(&game.get_json()).unwrap().get_draw_offer(), Some(Player::One));
+
+    game.apply(GameAction::Resign(Player::Two));
+    let loaded_game = Game::load(&game.get_json()).unwrap();
+    assert_eq!(loaded_game.get_status(), GameStatus::Win(Player::One, EndReason::Resignation));
+}
+
+#[test]
+fn placing_a_man_reports_the_placement_and_the_next_turn() {
+    let mut game = Game::new();
+    let events = game.play(game_move!(One, Hand, A7)).unwrap();
+    assert_eq!(events, vec!(
+        GameEvent::PiecePlaced(Player::One, Location::A7),
+        GameEvent::TurnChanged(Player::Two)
+    ));
+}
+
+#[test]
+fn a_rejected_move_reports_why() {
+    let mut game = Game::new();
+    assert_eq!(game.play(game_move!(Two, Hand, A7)), Err(MoveError::NotYourTurn));
+}
+
+#[test]
+fn closing_a_mill_reports_the_mill_and_the_capture() {
+    let mut game = Game::load(get_completable_game()).unwrap();
+    let events = game.play(game_move!(One, F4, G4, D7)).unwrap();
+    assert_eq!(events, vec!(
+        GameEvent::PieceMoved(Player::One, Location::F4, Location::G4),
+        GameEvent::MillFormed(Player::One, [Location::G7, Location::G4, Location::G1]),
+        GameEvent::PieceCaptured(Player::Two, Location::D7),
+        GameEvent::GameOver(GameStatus::Win(Player::One, EndReason::MillCapture))
+    ));
+}
+
+#[test]
+fn removals_that_take_nothing_report_no_captures() {
+    let heard = Arc::new(Mutex::new(vec!()));
+    let log = heard.clone();
+    let mut game = Game::load(get_completable_game()).unwrap();
+    game.add_listener(Arc::new(move |event: &GameEvent| log.lock().unwrap().push(*event)));
+
+    assert_eq!(game.play(game_move!(One, F4, G4, B6)), Err(MoveError::InvalidRemoval));
+    assert_eq!(game.play(game_move!(One, F4, G4, Hand)), Err(MoveError::InvalidRemoval));
+    assert!(heard.lock().unwrap().is_empty());
+
+    let events = game.play(game_move!(One, F4, G4, D7)).unwrap();
+    let captures: Vec<&GameEvent> = events.iter()
+        .filter(|event| matches!(**event, GameEvent::PieceCaptured(_, _)))
+        .collect();
+    assert_eq!(captures, vec!(&GameEvent::PieceCaptured(Player::Two, Location::D7)));
+}
+
+#[test]
+fn placing_the_last_man_starts_the_moving_phase() {
+    let mut game = Game::new();
+    let mut events = vec!();
+    while game.get_phase(Player::One) == Phase::Placing {
+        let player = game.get_next_player();
+        let to = Location::get_points().into_iter().find(|&location| {
+            game.validate(&GameMove::new(player, Location::Hand, location, None)).is_ok()
+                && game.get_capture_count(&GameMove::new(player, Location::Hand, location, None)) == 0
+        }).unwrap();
+        events = game.play(GameMove::new(player, Location::Hand, to, None)).unwrap();
+    }
+    assert!(events.contains(&GameEvent::PhaseChanged(Player::One, Phase::Moving)));
+    assert!(events.contains(&GameEvent::PhaseChanged(Player::Two, Phase::Moving)));
+}
+
+#[test]
+fn listeners_hear_about_moves_and_resignations() {
+    let heard = Arc::new(Mutex::new(vec!()));
+    let log = heard.clone();
+    let mut game = Game::new();
+    game.add_listener(Arc::new(move |event: &GameEvent| log.lock().unwrap().push(*event)));
+
+    game.submit(game_move!(One, Hand, A7));
+    game.apply(GameAction::Resign(Player::Two));
+    assert_eq!(*heard.lock().unwrap(), vec!(
+        GameEvent::PiecePlaced(Player::One, Location::A7),
+        GameEvent::TurnChanged(Player::Two),
+        GameEvent::GameOver(GameStatus::Win(Player::One, EndReason::Resignation))
+    ));
+}
+
+#[test]
+fn copies_of_a_game_are_played_without_its_listeners() {
+    let heard = Arc::new(Mutex::new(0));
+    let count = heard.clone();
+    let mut game = Game::new();
+    game.add_listener(Arc::new(move |_: &GameEvent| *count.lock().unwrap() += 1));
+
+    let mut copy = game.clone();
+    assert!(copy.submit(game_move!(One, Hand, A7)));
+    assert_eq!(*heard.lock().unwrap(), 0);
+    assert_eq!(game.get_turn(), 0);
+}
+
+#[test]
+fn every_empty_point_is_a_legal_placement_at_the_start() {
+    let game = Game::new();
+    let moves = game.get_legal_moves();
+    assert_eq!(moves.len(), 24);
+    assert!(moves.contains(&game_move!(One, Hand, A7)));
+}
+
+#[test]
+fn legal_moves_that_close_a_mill_include_a_removal() {
+    let game = Game::load(get_completable_game()).unwrap();
+    let moves = game.get_legal_moves();
+    assert!(moves.contains(&game_move!(One, F4, G4, D7)));
+    assert!(!moves.contains(&game_move!(One, F4, G4)));
+    assert!(moves.iter().all(|game_move| game.validate(game_move).is_ok()));
+}
+
+#[test]
+fn legal_moves_take_both_men_from_a_double_mill_in_an_order_that_works() {
+    let game = Game::load(get_double_mill_game()).unwrap();
+    let moves = game.get_legal_moves();
+    let to_a4: Vec<&GameMove> = moves.iter()
+        .filter(|game_move| game_move.get_to() == Location::A4)
+        .collect();
+    assert!(to_a4.contains(&&game_move!(One, Hand, A4, G7, D2)));
+    assert!(to_a4.iter().all(|game_move| game_move.get_removals().len() == 2));
+    assert!(moves.iter().all(|game_move| game.validate(game_move).is_ok()));
+}
+
+#[test]
+fn a_finished_game_has_no_legal_moves() {
+    let mut game = Game::new();
+    game.apply(GameAction::Resign(Player::One));
+    assert!(game.get_legal_moves().is_empty());
+}
+
+#[test]
+fn features_are_seen_from_the_side_to_move() {
+    let mut game = Game::new();
+    game.submit(game_move!(One, Hand, A7));
+    let features = game.encode_features();
+    assert_eq!(features.len(), FEATURE_COUNT);
+    // a7 is the first point, and belongs to the opponent of player two
+    assert_eq!(features[0], 0.0);
+    assert_eq!(features[24], 1.0);
+    assert_eq!(&features[72..], &[9.0, 8.0, 1.0, 0.0, 0.0, 1.0]);
+}
+
+#[test]
+fn features_decode_to_the_same_position() {
+    let game = Game::load(get_completable_game()).unwrap();
+    let decoded = Game::decode_features(&game.encode_features(), RuleSet::standard()).unwrap();
+    assert_eq!(decoded.encode_features(), game.encode_features());
+    assert_eq!(decoded.get_next_player(), Player::One);
+    assert_eq!(decoded.get_legal_moves(), game.get_legal_moves());
+}
+
+#[test]
+fn every_action_index_maps_back_to_its_move() {
+    for index in 0..ACTION_COUNT {
+        let game_move = GameMove::from_action_index(Player::Two, index).unwrap();
+        assert_eq!(game_move.get_action_index(), Some(index));
+    }
+    assert_eq!(GameMove::from_action_index(Player::One, ACTION_COUNT), None);
+    assert_eq!(game_move!(One, A4, B4, D7, G7).get_action_index(), None);
+}
+
+#[test]
+fn symmetries_keep_the_board_intact() {
+    let symmetries = Symmetry::all();
+    assert_eq!(symmetries.len(), 16);
+    assert_eq!(symmetries[0], Symmetry::identity());
+
+    for board in [STANDARD_BOARD, MORABARABA_BOARD].iter() {
+        for symmetry in symmetries.iter() {
+            for &(a, b) in board.get_edges().iter() {
+                assert!(board.is_adjacent(symmetry.apply(a), symmetry.apply(b)));
+            }
+            for mill in board.get_mills() {
+                let moved: Vec<Location> = mill.iter().map(|&point| symmetry.apply(point)).collect();
+                assert!(board.get_mills().iter().any(|other| moved.iter().all(|point| other.contains(point))));
+            }
+        }
+    }
+}
+
+#[test]
+fn symmetries_map_legal_moves_to_legal_moves() {
+    let mut game = Game::load(get_completable_game()).unwrap();
+    game.submit(game_move!(One, E4, E5));
+    let features = game.encode_features();
+
+    for symmetry in Symmetry::all() {
+        let transformed = Game::decode_features(&symmetry.apply_to_features(&features), RuleSet::standard()).unwrap();
+        for game_move in game.get_legal_moves() {
+            assert!(transformed.validate(&symmetry.apply_to_move(&game_move)).is_ok());
+            let index = game_move.get_action_index().unwrap();
+            let moved = symmetry.apply_to_action(Player::Two, index).unwrap();
+            assert_eq!(GameMove::from_action_index(Player::Two, moved), Some(symmetry.apply_to_move(&game_move)));
+        }
+    }
+}
+
+#[test]
+fn the_opening_book_counts_the_moves_played_from_each_position() {
+    let book = OpeningBook::from_games(&get_opening_games(), 2);
+    let start = Game::new();
+    assert_eq!(book.get_moves(&start), vec!((game_move!(One, Hand, A7), 2), (game_move!(One, Hand, D6), 1)));
+    assert_eq!(book.len(), 3);
+}
+
+#[test]
+fn the_opening_book_finds_symmetric_positions() {
+    let book = OpeningBook::from_games(&get_opening_games(), 2);
+    let mut game = Game::new();
+    // g1 is a7 turned half way round the board
+    game.submit(game_move!(One, Hand, G1));
+    assert_eq!(book.get_moves(&game), vec!((game_move!(Two, Hand, D1), 2)));
+}
+
+#[test]
+fn the_opening_book_keeps_variants_apart() {
+    let book = OpeningBook::from_games(&get_opening_games(), 2);
+    let mut game = Game::new();
+    game.submit(game_move!(One, Hand, A7));
+    // the same men on the board and in hand, with three of each side's taken under Morabaraba
+    let rules = Game::with_variant(Variant::Morabaraba).get_rules();
+    let morabaraba = Game::decode_features(&game.encode_features(), rules).unwrap();
+    assert_eq!(book.get_moves(&game).len(), 1);
+    assert!(book.get_moves(&morabaraba).is_empty());
+}
+
+#[test]
+fn opening_book_weights_stop_at_the_largest_count() {
+    let mut book = OpeningBook::new();
+    let game = Game::new();
+    book.add(&game, &game_move!(One, Hand, A7), u32::MAX);
+    book.add(&game, &game_move!(One, Hand, A7), 1);
+    assert_eq!(book.get_moves(&game), vec!((game_move!(One, Hand, A7), u32::MAX)));
+}
+
+#[test]
+fn book_moves_are_chosen_by_weight() {
+    let book = OpeningBook::from_games(&get_opening_games(), 1);
+    let start = Game::new();
+    assert_eq!(book.choose(&start, 0.0), Some(game_move!(One, Hand, A7)));
+    assert_eq!(book.choose(&start, 0.6), Some(game_move!(One, Hand, A7)));
+    assert_eq!(book.choose(&start, 0.7), Some(game_move!(One, Hand, D6)));
+    assert_eq!(book.choose(&start, 1.0), Some(game_move!(One, Hand, D6)));
+    assert_eq!(book.choose(&Game::with_variant(Variant::Morabaraba), 0.5), None);
+}
+
+#[test]
+fn the_opening_book_survives_a_round_trip_through_bytes() {
+    let book = OpeningBook::from_games(&get_opening_games(), 2);
+    let bytes = book.to_bytes();
+    assert_eq!(OpeningBook::from_bytes(&bytes), Ok(book));
+    assert!(OpeningBook::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    assert!(OpeningBook::from_bytes(b"not a book").is_err());
+}
+
+#[test]
+fn the_hint_closes_a_mill_when_it_can() {
+    let game = Game::load(get_completable_game()).unwrap();
+    let hint = game.hint().unwrap();
+    assert_eq!(hint.get_move(), game_move!(One, F4, G4, D7));
+    assert_eq!(hint.get_reason(), HintReason::ClosesMill([Location::G7, Location::G4, Location::G1]));
+}
+
+#[test]
+fn the_hint_blocks_the_opponents_mill() {
+    let mut game = Game::new();
+    game.submit(game_move!(One, Hand, A7));
+    game.submit(game_move!(Two, Hand, B6));
+    game.submit(game_move!(One, Hand, A4));
+    let hint = game.hint().unwrap();
+    assert_eq!(hint.get_move(), game_move!(Two, Hand, A1));
+    assert_eq!(hint.get_reason(), HintReason::BlocksMill([Location::A7, Location::A4, Location::A1]));
+}
+
+#[test]
+fn the_hint_sets_up_a_double_mill() {
+    let mut game = Game::new();
+    game.submit(game_move!(One, Hand, A4));
+    game.submit(game_move!(Two, Hand, F2));
+    game.submit(game_move!(One, Hand, D7));
+    game.submit(game_move!(Two, Hand, C3));
+    let hint = game.hint().unwrap();
+    assert_eq!(hint.get_move(), game_move!(One, Hand, A7));
+    match hint.get_reason() {
+        HintReason::SetsUpDoubleMill(first, second) => {
+            assert!(first.contains(&Location::A1) && second.contains(&Location::G7));
+        },
+        reason => panic!("unexpected reason {:?}", reason)
+    }
+}
+
+#[test]
+fn there_are_no_hints_once_the_game_is_over() {
+    let mut game = Game::new();
+    assert_eq!(game.hint().unwrap().get_reason(), HintReason::SafestMove);
+    game.apply(GameAction::Resign(Player::One));
+    assert_eq!(game.hint(), None);
+}
+
+#[test]
+fn threats_are_the_mills_a_player_could_close_next_move() {
+    let game = Game::load(get_completable_game()).unwrap();
+    let threats: Vec<(Location, [Location; 3])> = game.threats(Player::One).iter()
+        .map(|threat: &Threat| (threat.get_point(), threat.get_mill()))
+        .collect();
+    assert_eq!(threats, vec!(
+        (Location::G4, [Location::E4, Location::F4, Location::G4]),
+        (Location::G4, [Location::G7, Location::G4, Location::G1])
+    ));
+    assert!(game.threats(Player::Two).is_empty());
+}
+
+#[test]
+fn a_mill_with_a_man_free_to_step_out_is_open() {
+    let mut game = Game::load(get_completable_game()).unwrap();
+    assert!(game.open_mills(Player::One).is_empty());
+    game.submit(game_move!(One, F4, G4, D7));
+    assert_eq!(game.open_mills(Player::One), vec!([Location::G7, Location::G4, Location::G1]));
+}
+
+#[test]
+fn blocked_pieces_have_nowhere_to_go() {
+    let game = Game::load(get_blocked_game()).unwrap();
+    let blocked = game.blocked_pieces(Player::One);
+    assert_eq!(blocked.len(), 4);
+    for location in [Location::A7, Location::A4, Location::A1, Location::D7].iter() {
+        assert!(blocked.contains(location));
+    }
+    assert!(!game.blocked_pieces(Player::Two).contains(&Location::G7));
+}
+
+#[test]
+fn a_fork_leaves_two_mills_a_man_short() {
+    let mut game = Game::new();
+    game.submit(game_move!(One, Hand, A4));
+    game.submit(game_move!(Two, Hand, F2));
+    game.submit(game_move!(One, Hand, D7));
+    game.submit(game_move!(Two, Hand, C3));
+    assert_eq!(game.forks(Player::One), vec!(Location::A7));
+    assert!(game.forks(Player::Two).is_empty());
+}
+
+#[test]
+fn mills_lists_each_mill_on_the_board_with_its_owner() {
+    let mut game = Game::load(get_existing_mill()).unwrap();
+    assert_eq!(game.mills(), vec!((Player::Two, [Location::D3, Location::D2, Location::D1])));
+    game.submit(game_move!(One, F4, G4, F6));
+    assert_eq!(game.mills().len(), 2);
+    assert!(game.mills().contains(&(Player::One, [Location::G7, Location::G4, Location::G1])));
+}
+
+#[test]
+fn only_men_in_a_formed_mill_are_in_a_mill() {
+    let game = Game::load(get_existing_mill()).unwrap();
+    assert!(game.is_in_mill(Location::D2));
+    assert!(!game.is_in_mill(Location::D7));
+    assert!(!game.is_in_mill(Location::A7));
+    assert!(!game.is_in_mill(Location::B6));
+    assert!(!game.is_in_mill(Location::Captured));
+}
+
+#[test]
+fn men_in_mills_cant_be_removed_while_others_can() {
+    let game = Game::load(get_existing_mill()).unwrap();
+    assert_eq!(game.removable_pieces(Player::Two), vec!(Location::F6, Location::D7));
+
+    let game_json = with_rules(get_existing_mill(), json!({ "capture_from_mills": true }));
+    let game = Game::load(&game_json).unwrap();
+    assert_eq!(game.removable_pieces(Player::Two).len(), 5);
+
+    let only_mill = get_existing_mill().replace("\"F6\"", "\"Captured\"").replace("\"D7\"", "\"Captured\"");
+    let game = Game::load(&only_mill).unwrap();
+    assert_eq!(game.removable_pieces(Player::Two), vec!(Location::D3, Location::D1, Location::D2));
+}
+
+// ------------------------------------------------------------------------------------------------
+
+fn assert_piece_exists(pieces: &[Piece], player: Player, location: Location) {
+    assert!(pieces.iter().any(|&x| {
+        x.get_location() == location && x.get_player() == player
+    }), "piece doesn't seem to exist");
+}
+
+fn assert_piece_doesnt_exist(pieces: &[Piece], player: Player, location: Location) {
+    assert_eq!(pieces.iter().any(|&x| {
+        x.get_location() == location && x.get_player() == player
+    }), false, "piece exists");
+}
+
+fn get_json() -> &'static str {
+    r#"{
+        "history": [],
+        "pieces": [
+            { "location": "A7", "player": 1 },
+            { "location": "A1", "player": 1 },
+            { "location": "G1", "player": 1 },
+            { "location": "G7", "player": 1 },
+            { "location": "B6", "player": 1 },
+            { "location": "E4", "player": 1 },
+            { "location": "F4", "player": 1 },
+            { "location": "F2", "player": 1 },
+            { "location": "C5", "player": 1 },
+            { "location": "F6", "player": 2 },
+            { "location": "B4", "player": 2 },
+            { "location": "D7", "player": 2 },
+            { "location": "D1", "player": 2 },
+            { "location": "A4", "player": 2 },
+            { "location": "D5", "player": 2 },
+            { "location": "D2", "player": 2 },
+            { "location": "D6", "player": 2 },
+            { "location": "E3", "player": 2 }
+        ],
+        "player_turn": 1,
+        "rules": {
+            "blocked": "Loss",
+            "capture_from_mills": false,
+            "capture_per_mill": false,
+            "flying": true,
+            "reform_mill_immediately": true,
+            "threefold_repetition": true,
+            "variant": "NineMensMorris"
+        },
+        "turn": 2
+    }"#
+}
+
+// the shape games were saved in before RuleSet, with the variant at the top level and at
+// most one removal per move
+fn get_old_save() -> &'static str {
+    r#"{
+        "history": [
+            { "from": "Hand", "player": 1, "remove": null, "to": "A7" },
+            { "from": "Hand", "player": 2, "remove": null, "to": "D1" },
+            { "from": "Hand", "player": 1, "remove": "D1", "to": "B6" }
+        ],
+        "pieces": [
+            { "location": "A7", "player": 1 },
+            { "location": "B6", "player": 1 },
+            { "location": "Captured", "player": 2 }
+        ],
+        "player_turn": 2,
+        "turn": 3,
+        "variant": "Morabaraba"
+    }"#
+}
+
+fn get_late_game() -> &'static str {
+    r#"{
+        "pieces": [
+            { "player": 1, "location": "A7"},
+            { "player": 1, "location": "A1"},
+            { "player": 1, "location": "G1"},
+            { "player": 1, "location": "G7"},
+            { "player": 1, "location": "B6"},
+            { "player": 1, "location": "E4"},
+            { "player": 1, "location": "Captured"},
+            { "player": 1, "location": "Captured"},
+            { "player": 1, "location": "Captured"},
+            { "player": 2, "location": "F6"},
+            { "player": 2, "location": "B4"},
+            { "player": 2, "location": "D7"},
+            { "player": 2, "location": "D1"},
+            { "player": 2, "location": "A4"},
+            { "player": 2, "location": "Captured"},
+            { "player": 2, "location": "Captured"},
+            { "player": 2, "location": "Captured"},
+            { "player": 2, "location": "Captured"}
+        ],
+        "turn": 2,
+        "player_turn": 1
+    }"#
+}
+
+fn get_completable_game() -> &'static str {
+    r#"{
+        "pieces": [
+            { "player": 1, "location": "A7"},
+            { "player": 1, "location": "A1"},
+            { "player": 1, "location": "G1"},
+            { "player": 1, "location": "G7"},
+            { "player": 1, "location": "F4"},
+            { "player": 1, "location": "E4"},
+            { "player": 1, "location": "Captured"},
+            { "player": 1, "location": "Captured"},
+            { "player": 1, "location": "Captured"},
+            { "player": 2, "location": "F6"},
+            { "player": 2, "location": "Captured"},
+            { "player": 2, "location": "D7"},
+            { "player": 2, "location": "D1"},
+            { "player": 2, "location": "Captured"},
+            { "player": 2, "location": "Captured"},
+            { "player": 2, "location": "Captured"},
+            { "player": 2, "location": "Captured"},
+            { "player": 2, "location": "Captured"}
+        ],
+        "turn": 2,
+        "player_turn": 1
+    }"#
+}
+
+// player one can close two mills at a4, and player two's only man outside a mill is g7
+fn get_double_mill_game() -> &'static str {
+    r#"{
+        "pieces": [
+            { "player": 1, "location": "A7"},
+            { "player": 1, "location": "A1"},
+            { "player": 1, "location": "B4"},
+            { "player": 1, "location": "C4"},
+            { "player": 1, "location": "Hand"},
+            { "player": 1, "location": "Hand"},
+            { "player": 1, "location": "Hand"},
+            { "player": 1, "location": "Hand"},
+            { "player": 1, "location": "Hand"},
+            { "player": 2, "location": "D1"},
+            { "player": 2, "location": "D2"},
+            { "player": 2, "location": "D3"},
+            { "player": 2, "location": "G7"},
+            { "player": 2, "location": "Hand"},
+            { "player": 2, "location": "Hand"},
+            { "player": 2, "location": "Hand"},
+            { "player": 2, "location": "Hand"},
+            { "player": 2, "location": "Hand"}
+        ],
+        "turn": 8,
+        "player_turn": 1,
+        "rules": { "capture_per_mill": true }
+    }"#
+}
+
+fn get_existing_mill() -> &'static str {
+    r#"{
+        "pieces": [
+            { "player": 1, "location": "A7"},
+            { "player": 1, "location": "A1"},
+            { "player": 1, "location": "G1"},
+            { "player": 1, "location": "G7"},
+            { "player": 1, "location": "F4"},
+            { "player": 1, "location": "E4"},
+            { "player": 1, "location": "Captured"},
+            { "player": 1, "location": "Captured"},
+            { "player": 1, "location": "Captured"},
+            { "player": 2, "location": "F6"},
+            { "player": 2, "location": "D3"},
+            { "player": 2, "location": "D7"},
+            { "player": 2, "location": "D1"},
+            { "player": 2, "location": "D2"},
+            { "player": 2, "location": "Captured"},
+            { "player": 2, "location": "Captured"},
+            { "player": 2, "location": "Captured"},
+            { "player": 2, "location": "Captured"}
+        ],
+        "turn": 2,
+        "player_turn": 1
+    }"#
+}
+
+fn get_morabaraba_game() -> &'static str {
+    r#"{
+        "pieces": [
+            { "player": 1, "location": "A7"},
+            { "player": 1, "location": "A4"},
+            { "player": 1, "location": "A1"},
+            { "player": 1, "location": "E4"},
+            { "player": 1, "location": "Captured"},
+            { "player": 1, "location": "Captured"},
+            { "player": 1, "location": "Captured"},
+            { "player": 1, "location": "Captured"},
+            { "player": 1, "location": "Captured"},
+            { "player": 1, "location": "Captured"},
+            { "player": 1, "location": "Captured"},
+            { "player": 1, "location": "Captured"},
+            { "player": 2, "location": "D7"},
+            { "player": 2, "location": "D1"},
+            { "player": 2, "location": "F6"},
+            { "player": 2, "location": "E3"},
+            { "player": 2, "location": "Captured"},
+            { "player": 2, "location": "Captured"},
+            { "player": 2, "location": "Captured"},
+            { "player": 2, "location": "Captured"},
+            { "player": 2, "location": "Captured"},
+            { "player": 2, "location": "Captured"},
+            { "player": 2, "location": "Captured"},
+            { "player": 2, "location": "Captured"}
+        ],
+        "turn": 2,
+        "player_turn": 1,
+        "rules": { "variant": "Morabaraba" }
+    }"#
+}
+
+fn get_morabaraba_flying_game() -> &'static str {
+    r#"{
+        "pieces": [
+            { "player": 1, "location": "A7"},
+            { "player": 1, "location": "A4"},
+            { "player": 1, "location": "A1"},
+            { "player": 1, "location": "Captured"},
+            { "player": 1, "location": "Captured"},
+            { "player": 1, "location": "Captured"},
+            { "player": 1, "location": "Captured"},
+            { "player": 1, "location": "Captured"},
+            { "player": 1, "location": "Captured"},
+            { "player": 1, "location": "Captured"},
+            { "player": 1, "location": "Captured"},
+            { "player": 1, "location": "Captured"},
+            { "player": 2, "location": "D7"},
+            { "player": 2, "location": "D1"},
+            { "player": 2, "location": "F6"},
+            { "player": 2, "location": "E3"},
+            { "player": 2, "location": "Captured"},
+            { "player": 2, "location": "Captured"},
+            { "player": 2, "location": "Captured"},
+            { "player": 2, "location": "Captured"},
+            { "player": 2, "location": "Captured"},
+            { "player": 2, "location": "Captured"},
+            { "player": 2, "location": "Captured"},
+            { "player": 2, "location": "Captured"}
+        ],
+        "turn": 2,
+        "player_turn": 1,
+        "rules": { "variant": "Morabaraba" }
+    }"#
+}
+
+fn with_rules(game: &str, rules: Value) -> String {
+    let mut game: Value = serde_json::from_str(game).unwrap();
+    game["rules"] = rules;
+    game.to_string()
+}
+
+fn get_flying_game() -> &'static str {
+    r#"{
+        "pieces": [
+            { "player": 1, "location": "A7"},
+            { "player": 1, "location": "A4"},
+            { "player": 1, "location": "A1"},
+            { "player": 1, "location": "Captured"},
+            { "player": 1, "location": "Captured"},
+            { "player": 1, "location": "Captured"},
+            { "player": 1, "location": "Captured"},
+            { "player": 1, "location": "Captured"},
+            { "player": 1, "location": "Captured"},
+            { "player": 2, "location": "D7"},
+            { "player": 2, "location": "D1"},
+            { "player": 2, "location": "F6"},
+            { "player": 2, "location": "E3"},
+            { "player": 2, "location": "Captured"},
+            { "player": 2, "location": "Captured"},
+            { "player": 2, "location": "Captured"},
+            { "player": 2, "location": "Captured"},
+            { "player": 2, "location": "Captured"}
+        ],
+        "turn": 2,
+        "player_turn": 1
+    }"#
+}
+
+fn get_blocked_game() -> &'static str {
+    r#"{
+        "pieces": [
+            { "player": 1, "location": "A7"},
+            { "player": 1, "location": "A4"},
+            { "player": 1, "location": "A1"},
+            { "player": 1, "location": "D7"},
+            { "player": 1, "location": "Captured"},
+            { "player": 1, "location": "Captured"},
+            { "player": 1, "location": "Captured"},
+            { "player": 1, "location": "Captured"},
+            { "player": 1, "location": "Captured"},
+            { "player": 2, "location": "G7"},
+            { "player": 2, "location": "D1"},
+            { "player": 2, "location": "G1"},
+            { "player": 2, "location": "B4"},
+            { "player": 2, "location": "C4"},
+            { "player": 2, "location": "D6"},
+            { "player": 2, "location": "D5"},
+            { "player": 2, "location": "Captured"},
+            { "player": 2, "location": "Captured"}
+        ],
+        "turn": 2,
+        "player_turn": 1
+    }"#
+}
+
+fn play_into_double_mill(game: &mut Game) {
+    game.submit(game_move!(One, Hand, A7));
+    game.submit(game_move!(Two, Hand, G4));
+    game.submit(game_move!(One, Hand, A1));
+    game.submit(game_move!(Two, Hand, G1));
+    game.submit(game_move!(One, Hand, B4));
+    game.submit(game_move!(Two, Hand, E3));
+    game.submit(game_move!(One, Hand, C4));
+    game.submit(game_move!(Two, Hand, D3));
+}
+
+fn get_small_board() -> &'static str {
+    "# a single mill with a spur
+    points: A7 D7 G7 D5
+    edges: A7-D7 D7-G7 D5-A7
+    mills: A7-D7-G7"
+}
+
+// player one has a man at `from` with `to` left empty, and nobody is able to fly
+fn get_sliding_game(from: Location, to: Location) -> String {
+    let others: Vec<Location> = Location::get_points().into_iter().filter(|&location| {
+        location != from && location != to
+    }).collect();
+    let pieces = [
+        (1, from), (1, others[0]), (1, others[1]),
+        (2, others[2]), (2, others[3]), (2, others[4])
+    ];
+
+    json!({
+        "pieces": pieces.iter().map(|&(player, location)| {
+            json!({ "player": player, "location": location.to_str() })
+        }).collect::<Vec<Value>>(),
+        "turn": 12,
+        "player_turn": 1,
+        "rules": { "flying": false }
+    }).to_string()
+}
+
+// written out by hand from the board in the README, rather than taken from the board's edges
+fn get_standard_neighbors(location: Location) -> Vec<Location> {
+    use Location::*;
+    match location {
+        A7          => vec!(A4, D7),
+        A4          => vec!(A7, A1, B4),
+        A1          => vec!(A4, D1),
+        B6          => vec!(B4, D6),
+        B4          => vec!(B6, B2, A4, C4),
+        B2          => vec!(B4, D2),
+        C5          => vec!(C4, D5),
+        C4          => vec!(C5, C3, B4),
+        C3          => vec!(C4, D3),
+        D7          => vec!(A7, G7, D6),
+        D6          => vec!(B6, F6, D7, D5),
+        D5          => vec!(C5, E5, D6),
+        D3          => vec!(C3, E3, D2),
+        D2          => vec!(B2, F2, D3, D1),
+        D1          => vec!(A1, G1, D2),
+        E5          => vec!(E4, D5),
+        E4          => vec!(E5, E3, F4),
+        E3          => vec!(E4, D3),
+        F6          => vec!(F4, D6),
+        F4          => vec!(F6, F2, E4, G4),
+        F2          => vec!(F4, D2),
+        G7          => vec!(G4, D7),
+        G4          => vec!(G7, G1, F4),
+        G1          => vec!(G4, D1),
+        Hand | Captured => vec!()
+    }
+}
+
+#[derive(Default)]
+struct FakeTime(AtomicU64);
+
+impl FakeTime {
+    fn advance(&self, duration: Duration) {
+        self.0.fetch_add(duration.as_millis() as u64, Ordering::SeqCst);
+    }
+}
+
+impl TimeSource for FakeTime {
+    fn now(&self) -> Duration {
+        Duration::from_millis(self.0.load(Ordering::SeqCst))
+    }
+}
+
+fn secs(seconds: u64) -> Duration {
+    Duration::from_secs(seconds)
+}
+
+// three games that open a7 d7, a7 d7 and d6 a7
+fn get_opening_games() -> Vec<Game> {
+    [("A7", "D7"), ("A7", "D7"), ("D6", "A7")].iter().map(|&(first, second)| {
+        let mut game = Game::new();
+        game.submit(GameMove::new(Player::One, Location::Hand, Location::from_str(first), None));
+        game.submit(GameMove::new(Player::Two, Location::Hand, Location::from_str(second), None));
+        game
+    }).collect()
 }
@@ -0,0 +1,130 @@
+use Game;
+use Location;
+use Player;
+
+// an empty point where a player could close a mill on their next move
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct Threat {
+    point: Location,
+    mill: [Location; 3]
+}
+
+impl Threat {
+    pub fn get_point(&self) -> Location {
+        self.point
+    }
+
+    // the three points, in the order the board lists them
+    pub fn get_mill(&self) -> [Location; 3] {
+        self.mill
+    }
+}
+
+impl Game {
+    // each mill the player could close on their next move; a point that would close two
+    // mills at once is listed once for each
+    pub fn threats(&self, player: Player) -> Vec<Threat> {
+        self.board.get_points().into_iter().flat_map(|point| {
+            self.get_mills_closed(player, point).into_iter().map(move |mill| Threat { point, mill })
+        }).collect()
+    }
+
+    // the player's mills with a man that could step out now and back in on the move after,
+    // forming the mill again
+    pub fn open_mills(&self, player: Player) -> Vec<[Location; 3]> {
+        if self.is_in_placement_phase() {
+            return vec!();
+        }
+
+        self.board.get_mills().into_iter().filter(|mill| {
+            mill.iter().all(|&point| self.does_piece_exist(point, player))
+                && mill.iter().any(|&point| self.can_leave(player, point))
+        }).collect()
+    }
+
+    // the player's men on the board with nowhere to go. While placing, men are judged by
+    // the points next to them, since that's how they'll move once every man is down.
+    pub fn blocked_pieces(&self, player: Player) -> Vec<Location> {
+        self.pieces.iter()
+            .filter(|piece| piece.get_player() == player)
+            .map(|piece| piece.get_location())
+            .filter(|&location| self.board.contains(location) && !self.can_leave(player, location))
+            .collect()
+    }
+
+    // empty points the player could get a man to next move that would leave two of their
+    // mills a man short at once, so that only one can be blocked
+    pub fn forks(&self, player: Player) -> Vec<Location> {
+        self.board.get_points().into_iter().filter(|&point| {
+            if self.is_location_occupied(point) {
+                return false;
+            }
+
+            let opened = self.get_rows(point).into_iter().filter(|&(a, b)| {
+                let own = |location| self.does_piece_exist(location, player);
+                (own(a) && !self.is_location_occupied(b)) || (own(b) && !self.is_location_occupied(a))
+            }).filter(|&(a, b)| {
+                // the man getting there can't be the one the mill is waiting on
+                self.can_reach(player, point, &[a, b])
+            }).count();
+
+            opened >= 2
+        }).collect()
+    }
+
+    // the mills the player could close on their next move, as the three points of each
+    pub(crate) fn get_mill_threats(&self, player: Player) -> Vec<[Location; 3]> {
+        self.board.get_points().into_iter().filter_map(|point| {
+            self.get_mill_closed(player, point)
+        }).collect()
+    }
+
+    // the mill the player would close by getting a man to the empty point next move
+    pub(crate) fn get_mill_closed(&self, player: Player, point: Location) -> Option<[Location; 3]> {
+        self.get_mills_closed(player, point).into_iter().next()
+    }
+
+    // the points in the order the board lists them
+    pub(crate) fn get_mill(&self, points: &[Location]) -> Option<[Location; 3]> {
+        self.board.get_mills().into_iter().find(|mill| points.iter().all(|point| mill.contains(point)))
+    }
+
+    // whether a man other than those in `staying` could get to the point
+    pub(crate) fn can_reach(&self, player: Player, point: Location, staying: &[Location]) -> bool {
+        let in_hand = self.pieces.iter().any(|piece| {
+            piece.get_player() == player && piece.get_location() == Location::Hand
+        });
+        if in_hand {
+            return true;
+        }
+
+        !self.is_in_placement_phase() && self.pieces.iter().any(|piece| {
+            let location = piece.get_location();
+            piece.get_player() == player
+                && self.board.contains(location)
+                && !staying.contains(&location)
+                && (self.is_flying(player) || self.is_next_door(point, location))
+        })
+    }
+
+    // every mill the player would close by getting a man to the empty point next move
+    fn get_mills_closed(&self, player: Player, point: Location) -> Vec<[Location; 3]> {
+        if self.is_location_occupied(point) || !self.is_three_in_a_row(player, point) {
+            return vec!();
+        }
+
+        self.get_rows(point).into_iter().filter(|&(a, b)| {
+            self.does_piece_exist(a, player) && self.does_piece_exist(b, player)
+                && self.can_reach(player, point, &[a, b])
+        }).filter_map(|(a, b)| self.get_mill(&[a, point, b])).collect()
+    }
+
+    // whether the man on the point has somewhere to go, ignoring whose turn it is
+    fn can_leave(&self, player: Player, location: Location) -> bool {
+        let empty = |point: &Location| !self.is_location_occupied(*point);
+        if self.is_flying(player) && !self.is_in_placement_phase() {
+            return self.board.get_points().iter().any(empty);
+        }
+        self.board.get_neighbors(location).iter().any(empty)
+    }
+}
@@ -0,0 +1,45 @@
+use std::str::FromStr;
+use board::{Board, STANDARD_BOARD, MORABARABA_BOARD};
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Variant {
+    NineMensMorris,
+    Morabaraba
+}
+
+impl FromStr for Variant {
+    type Err = String;
+
+    fn from_str(code: &str) -> Result<Variant, String> {
+        use Variant::*;
+        match code {
+            "NineMensMorris"    => Ok(NineMensMorris),
+            "Morabaraba"        => Ok(Morabaraba),
+            _                   => Err(format!("unknown variant \"{}\"", code))
+        }
+    }
+}
+
+impl Variant {
+    pub fn to_str(&self) -> &'static str {
+        use Variant::*;
+        match *self {
+            NineMensMorris  => "NineMensMorris",
+            Morabaraba      => "Morabaraba"
+        }
+    }
+
+    pub fn get_piece_count(&self) -> usize {
+        match *self {
+            Variant::NineMensMorris => 9,
+            Variant::Morabaraba     => 12
+        }
+    }
+
+    pub fn get_board(&self) -> Board {
+        match *self {
+            Variant::NineMensMorris => STANDARD_BOARD,
+            Variant::Morabaraba     => MORABARABA_BOARD
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use wasm_bindgen::prelude::*;
+use {Game, GameMove, GameStatus, Player, Variant};
+
+// the game as JavaScript sees it: moves go in and out in the nmm binary's notation,
+// e.g. "a7", "a7-a4" or "a7-a4xd2", and players are the numbers 1 and 2
+#[wasm_bindgen(js_name = Game)]
+pub struct WasmGame {
+    game: Game
+}
+
+#[wasm_bindgen(js_class = Game)]
+impl WasmGame {
+    #[wasm_bindgen(constructor)]
+    pub fn new(morabaraba: Option<bool>) -> WasmGame {
+        let variant = match morabaraba {
+            Some(true) => Variant::Morabaraba,
+            _ => Variant::NineMensMorris
+        };
+        WasmGame { game: Game::with_variant(variant) }
+    }
+
+    pub fn load(json: &str) -> Result<WasmGame, JsError> {
+        let game = Game::load(json).map_err(|e| JsError::new(&e.to_string()))?;
+        Ok(WasmGame { game })
+    }
+
+    #[wasm_bindgen(js_name = getJson)]
+    pub fn get_json(&self) -> String {
+        self.game.get_json()
+    }
+
+    // false if the move couldn't be read, or was turned down
+    pub fn submit(&mut self, notation: &str) -> bool {
+        match GameMove::from_notation(self.game.get_next_player(), notation) {
+            Some(game_move) => self.game.submit(game_move),
+            None => false
+        }
+    }
+
+    // why submit would turn the move down, or undefined if it wouldn't
+    pub fn validate(&self, notation: &str) -> Option<String> {
+        match GameMove::from_notation(self.game.get_next_player(), notation) {
+            Some(game_move) => self.game.validate(&game_move).err().map(|e| e.to_string()),
+            None => Some(format!("didn't understand \"{}\"", notation))
+        }
+    }
+
+    #[wasm_bindgen(js_name = legalMoves)]
+    pub fn legal_moves(&self) -> Vec<String> {
+        self.game.get_legal_moves().iter().map(|game_move| game_move.to_string()).collect()
+    }
+
+    #[wasm_bindgen(js_name = nextPlayer)]
+    pub fn next_player(&self) -> u8 {
+        Self::number(self.game.get_next_player())
+    }
+
+    // "playing", "win" or "draw"
+    pub fn status(&self) -> String {
+        let status = match self.game.get_status() {
+            GameStatus::Playing => "playing",
+            GameStatus::Win(_, _) => "win",
+            GameStatus::Draw(_) => "draw"
+        };
+        String::from(status)
+    }
+
+    pub fn winner(&self) -> Option<u8> {
+        match self.game.get_status() {
+            GameStatus::Win(player, _) => Some(Self::number(player)),
+            _ => None
+        }
+    }
+
+    // why the game ended, e.g. "MillCapture" or "Repetition"
+    #[wasm_bindgen(js_name = endReason)]
+    pub fn end_reason(&self) -> Option<String> {
+        match self.game.get_status() {
+            GameStatus::Playing => None,
+            GameStatus::Win(_, reason) | GameStatus::Draw(reason) => Some(String::from(reason.to_str()))
+        }
+    }
+
+    fn number(player: Player) -> u8 {
+        Game::wrap_player(player) as u8
+    }
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "capi")]
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+// the C program is built against include/nmm.h and the crate's shared library. The library
+// gets its own target directory, since builds without capi write a shared library of the same
+// name into the usual one.
+#[test]
+fn the_c_interface_plays_a_game() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = env::var_os("CARGO_TARGET_DIR")
+        .map(|dir| root.join(dir))
+        .unwrap_or_else(|| root.join("target"))
+        .join("capi");
+    // the library is built with the same profile as the tests, as near as they can tell
+    let (profile, flags): (&str, &[&str]) = if cfg!(debug_assertions) {
+        ("debug", &[])
+    } else {
+        ("release", &["--release"])
+    };
+    let library_dir = target_dir.join(profile);
+    let program = library_dir.join("nmm-capi-test");
+
+    let built = Command::new(env::var("CARGO").unwrap_or_else(|_| String::from("cargo")))
+        .current_dir(&root)
+        .args(["build", "--lib", "--features", "capi", "--target-dir"])
+        .arg(&target_dir)
+        .args(flags)
+        .status()
+        .expect("couldn't run cargo");
+    assert!(built.success());
+
+    let compiled = Command::new(env::var("CC").unwrap_or_else(|_| String::from("cc")))
+        .arg(root.join("tests/capi/game.c"))
+        .arg("-I").arg(root.join("include"))
+        .arg("-L").arg(&library_dir)
+        .arg(format!("-Wl,-rpath,{}", library_dir.display()))
+        .args(["-lnmm_lib", "-Wall", "-Werror", "-o"])
+        .arg(&program)
+        .status()
+        .expect("couldn't run the C compiler");
+    assert!(compiled.success());
+
+    // cargo test points LD_LIBRARY_PATH at its own deps, which would win over the rpath
+    let output = Command::new(&program).env("LD_LIBRARY_PATH", &library_dir).output().unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "");
+    assert!(output.status.success());
+}
+
+// regenerating needs the cbindgen command, so without it the header goes unchecked
+#[test]
+fn the_header_matches_the_rust_interface() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let generated = match Command::new("cbindgen")
+        .current_dir(&root)
+        .args(["--config", "cbindgen.toml", "--crate", "nmm_lib", "--quiet"])
+        .output() {
+        Ok(output) => output,
+        Err(_) => {
+            eprintln!("cbindgen isn't installed, so include/nmm.h wasn't checked");
+            return;
+        }
+    };
+    assert!(generated.status.success(), "{}", String::from_utf8_lossy(&generated.stderr));
+
+    let header = fs::read_to_string(root.join("include/nmm.h")).unwrap();
+    assert!(
+        String::from_utf8_lossy(&generated.stdout) == header,
+        "include/nmm.h is out of date; regenerate it with \
+         `cbindgen --config cbindgen.toml --crate nmm_lib -o include/nmm.h`"
+    );
+}
@@ -0,0 +1,41 @@
+#include <stdio.h>
+#include <string.h>
+#include "nmm.h"
+
+#define CHECK(condition) do { \
+    if (!(condition)) { \
+        fprintf(stderr, "%s:%d: failed: %s\n", __FILE__, __LINE__, #condition); \
+        return 1; \
+    } \
+} while (0)
+
+int main(void) {
+    NmmGame *game = nmm_game_new(0);
+    CHECK(game != NULL);
+    CHECK(nmm_game_legal_move_count(game) == 24);
+    CHECK(nmm_game_legal_move(game, 24) == NULL);
+
+    CHECK(nmm_game_submit(game, "a7") == NMM_RESULT_OK);
+    CHECK(nmm_game_next_player(game) == 2);
+    CHECK(nmm_game_submit(game, "a7") == NMM_RESULT_OCCUPIED);
+    CHECK(nmm_game_submit(game, "zz") == NMM_RESULT_UNREADABLE);
+    CHECK(nmm_game_legal_move_count(game) == 23);
+    CHECK(strcmp(nmm_game_legal_move(game, 0), "a4") == 0);
+
+    char *json = nmm_game_get_json(game);
+    NmmGame *loaded = nmm_game_load(json);
+    nmm_string_free(json);
+    CHECK(loaded != NULL);
+    CHECK(nmm_game_next_player(loaded) == 2);
+    CHECK(nmm_game_status(loaded) == NMM_STATUS_PLAYING);
+    CHECK(nmm_game_end_reason(loaded) == NMM_END_REASON_NONE);
+
+    CHECK(nmm_game_load("not a game") == NULL);
+    CHECK(nmm_game_load("{}") == NULL);
+    CHECK(nmm_game_load("{\"pieces\": [{\"player\": 3, \"location\": \"Hand\"}]}") == NULL);
+
+    nmm_game_free(loaded);
+    nmm_game_free(game);
+    printf("ok\n");
+    return 0;
+}
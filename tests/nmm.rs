@@ -0,0 +1,47 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+fn play(commands: &str) -> String {
+    let mut nmm = Command::new(env!("CARGO_BIN_EXE_nmm"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    nmm.stdin.take().unwrap().write_all(commands.as_bytes()).unwrap();
+    let output = nmm.wait_with_output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn moves_are_played_in_turn() {
+    let output = play("a7\nd7\nquit\n");
+    assert!(output.contains("7 1----------(2)----------o"));
+    assert!(output.contains("player 1 to move"));
+}
+
+#[test]
+fn rejected_moves_are_explained() {
+    let output = play("a7\na7\nzz\n");
+    assert!(output.contains("can't play a7: that point is already taken"));
+    assert!(output.contains("didn't understand \"zz\""));
+}
+
+#[test]
+fn moves_can_be_taken_back() {
+    let output = play("a7\nundo\nundo\n");
+    assert!(output.contains("player 1: 9 in hand"));
+    assert!(output.contains("there's nothing to undo"));
+}
+
+#[test]
+fn games_can_be_saved_and_loaded() {
+    let path = std::env::temp_dir().join(format!("nmm-test-{}.json", std::process::id()));
+    let path = path.to_str().unwrap();
+    let output = play(&format!("a7\nsave {0}\nnew\nload {0}\n", path));
+    std::fs::remove_file(path).unwrap();
+
+    assert!(output.contains(&format!("saved to {}", path)));
+    assert!(output.trim_end().ends_with("player 2>"));
+}
@@ -0,0 +1,170 @@
+#![cfg(feature = "server")]
+
+extern crate serde_json;
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::process::{Child, Command, Stdio};
+use serde_json::Value;
+
+struct Server {
+    process: Child,
+    address: String
+}
+
+impl Server {
+    fn start() -> Server {
+        let mut process = Command::new(env!("CARGO_BIN_EXE_nmm-server"))
+            .args(["--port", "0"])
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        let mut line = String::new();
+        BufReader::new(process.stdout.as_mut().unwrap()).read_line(&mut line).unwrap();
+        let address = line.trim().trim_start_matches("listening on ").to_string();
+        Server { process, address }
+    }
+
+    fn connect(&self) -> Client {
+        let stream = TcpStream::connect(&self.address).unwrap();
+        Client {
+            reader: BufReader::new(stream.try_clone().unwrap()),
+            stream
+        }
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.process.kill();
+        let _ = self.process.wait();
+    }
+}
+
+struct Client {
+    stream: TcpStream,
+    reader: BufReader<TcpStream>
+}
+
+impl Client {
+    fn send(&mut self, request: &str) {
+        writeln!(self.stream, "{}", request).unwrap();
+    }
+
+    fn receive(&mut self) -> Value {
+        let mut line = String::new();
+        self.reader.read_line(&mut line).unwrap();
+        serde_json::from_str(&line).unwrap()
+    }
+
+    // joins a game, and returns the reply without the game's state that follows it
+    fn sit(&mut self, request: &str) -> Value {
+        self.send(request);
+        let reply = self.receive();
+        if reply["event"] != "error" {
+            assert_eq!(self.receive()["event"], "update");
+        }
+        reply
+    }
+}
+
+// two players sat at a new game
+fn start_game(server: &Server) -> (Client, Client, u64) {
+    let mut one = server.connect();
+    let reply = one.sit(r#"{ "command": "create" }"#);
+    let id = reply["game"].as_u64().unwrap();
+
+    let mut two = server.connect();
+    two.sit(&format!(r#"{{ "command": "join", "game": {} }}"#, id));
+    (one, two, id)
+}
+
+#[test]
+fn creating_a_game_seats_the_creator_as_player_1() {
+    let server = Server::start();
+    let mut client = server.connect();
+    let reply = client.sit(r#"{ "command": "create" }"#);
+    assert_eq!(reply["event"], "joined");
+    assert_eq!(reply["player"], 1);
+}
+
+#[test]
+fn moves_are_pushed_to_both_players_and_spectators() {
+    let server = Server::start();
+    let (mut one, mut two, id) = start_game(&server);
+    let mut spectator = server.connect();
+    spectator.sit(&format!(r#"{{ "command": "spectate", "game": {} }}"#, id));
+
+    one.send(r#"{ "command": "move", "move": "a7" }"#);
+    for client in [&mut one, &mut two, &mut spectator].iter_mut() {
+        let update = client.receive();
+        assert_eq!(update["event"], "update");
+        assert_eq!(update["state"]["turn"], 1);
+        assert_eq!(update["state"]["history"][0]["to"], "A7");
+    }
+}
+
+#[test]
+fn illegal_moves_are_rejected_with_the_reason() {
+    let server = Server::start();
+    let (_one, mut two, _) = start_game(&server);
+    two.send(r#"{ "command": "move", "move": "a7" }"#);
+    let reply = two.receive();
+    assert_eq!(reply["event"], "error");
+    assert_eq!(reply["message"], "can't play a7: it's the other player's turn");
+}
+
+#[test]
+fn a_game_only_has_room_for_two_players() {
+    let server = Server::start();
+    let (_one, _two, id) = start_game(&server);
+    let mut third = server.connect();
+    let reply = third.sit(&format!(r#"{{ "command": "join", "game": {} }}"#, id));
+    assert_eq!(reply["message"], format!("game {} already has two players", id));
+}
+
+#[test]
+fn games_are_played_independently() {
+    let server = Server::start();
+    let (mut one, _two, first) = start_game(&server);
+    let (mut three, _four, second) = start_game(&server);
+    assert!(first != second);
+
+    one.send(r#"{ "command": "move", "move": "a7" }"#);
+    three.send(r#"{ "command": "move", "move": "a7" }"#);
+    assert_eq!(one.receive()["state"]["history"][0]["to"], "A7");
+    assert_eq!(three.receive()["state"]["history"][0]["to"], "A7");
+}
+
+#[test]
+fn resigning_ends_the_game_for_everyone() {
+    let server = Server::start();
+    let (mut one, mut two, _) = start_game(&server);
+    one.send(r#"{ "command": "resign" }"#);
+    let status = two.receive()["status"].clone();
+    assert_eq!(status["status"], "Win");
+    assert_eq!(status["player"], 2);
+    assert_eq!(status["reason"], "Resignation");
+    assert_eq!(one.receive()["status"], status);
+}
+
+#[test]
+fn games_nobody_is_in_are_dropped() {
+    let server = Server::start();
+    let mut one = server.connect();
+    let first = one.sit(r#"{ "command": "create" }"#)["game"].as_u64().unwrap();
+    one.sit(r#"{ "command": "create", "variant": "Morabaraba" }"#);
+
+    let mut two = server.connect();
+    let reply = two.sit(&format!(r#"{{ "command": "join", "game": {} }}"#, first));
+    assert_eq!(reply["message"], format!("there's no game {}", first));
+}
+
+#[test]
+fn unknown_variants_are_rejected() {
+    let server = Server::start();
+    let mut client = server.connect();
+    let reply = client.sit(r#"{ "command": "create", "variant": "Lasca" }"#);
+    assert_eq!(reply["message"], "unknown variant \"Lasca\"");
+}
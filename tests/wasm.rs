@@ -0,0 +1,48 @@
+#![cfg(all(feature = "wasm", target_arch = "wasm32"))]
+
+extern crate nmm_lib;
+extern crate wasm_bindgen_test;
+
+use nmm_lib::wasm::WasmGame;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn moves_are_submitted_as_notation() {
+    let mut game = WasmGame::new(None);
+    assert!(game.submit("a7"));
+    assert_eq!(game.next_player(), 2);
+    assert!(!game.submit("a7"));
+    assert!(!game.submit("nonsense"));
+}
+
+#[wasm_bindgen_test]
+fn rejected_moves_are_explained() {
+    let mut game = WasmGame::new(None);
+    game.submit("a7");
+    assert_eq!(game.validate("a7"), Some(String::from("that point is already taken")));
+    assert_eq!(game.validate("d7"), None);
+}
+
+#[wasm_bindgen_test]
+fn legal_moves_are_listed_as_notation() {
+    let game = WasmGame::new(Some(true));
+    let moves = game.legal_moves();
+    assert_eq!(moves.len(), 24);
+    assert!(moves.contains(&String::from("a7")));
+}
+
+#[wasm_bindgen_test]
+fn games_survive_a_round_trip_through_json() {
+    let mut game = WasmGame::new(None);
+    game.submit("a7");
+    let loaded_game = WasmGame::load(&game.get_json()).unwrap();
+    assert_eq!(loaded_game.get_json(), game.get_json());
+    assert_eq!(loaded_game.status(), "playing");
+    assert_eq!(loaded_game.winner(), None);
+}
+
+#[wasm_bindgen_test]
+fn malformed_saves_are_errors() {
+    assert!(WasmGame::load("not a game").is_err());
+    assert!(WasmGame::load("{}").is_err());
+}